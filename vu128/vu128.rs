@@ -74,8 +74,14 @@
 //! The `encode_*` functions in this module will not generate such over-long
 //! encodings, but the `decode_*` functions will accept them. This is intended
 //! to allow `vu128` values to be placed in a buffer before the value to be
-//! written is known. Applications that require a single canonical encoding for
-//! any given value should perform appropriate checking in their own code.
+//! written is known, for example by the `encode_*_padded` functions.
+//! Applications that require a single canonical encoding for
+//! any given value should use the `decode_*_canonical` functions, which reject
+//! over-long encodings.
+//!
+//! An encoding longer than a type's maximum encoded length is an error for
+//! the `decode_*_checked` and `decode_*_from_slice` functions, even if its
+//! extra bytes are zero. Both report it as [`DecodeErrorKind::Overflow`].
 //!
 //! # Signed integers and floating-point values
 //!
@@ -88,13 +94,132 @@
 //! encoding] for signed integers and reverse-endian layout for floating-point.
 //!
 //! ["ZigZag" encoding]: https://protobuf.dev/programming-guides/encoding/#signed-ints
+//!
+//! # Cargo features
+//!
+//! * `alloc`: Provides the `VecVu128Ext` extension trait for `Vec<u8>`.
+//!
+//! * `std`: Implements `std::error::Error` for [`DecodeError`],
+//!   [`DecodeSliceError`], and [`EncodeError`], implements `std::io::Read` for
+//!   [`EncodeIter`], and provides the `WriteVu128Ext`, `ReadVu128Ext`, and
+//!   `BufReadVu128Ext` extension traits for `std::io`. `BufReadVu128Ext` has
+//!   the same `read_*_vu128` methods as `ReadVu128Ext`, but decodes directly
+//!   from a `BufRead` type's internal buffer. On x86_64, enables runtime
+//!   detection of SSE4.1 and AVX2 for [`decode_u32_slice`]. Implies `alloc`.
 
 #![no_std]
 #![warn(clippy::must_use_candidate)]
 #![warn(clippy::undocumented_unsafe_blocks)]
 #![warn(missing_docs)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::convert::TryFrom;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
 use core::mem;
+use core::mem::MaybeUninit;
+use core::num::{
+	NonZeroU128,
+	NonZeroU32,
+	NonZeroU64,
+};
+use core::slice;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use std::io;
+
+/// The maximum length of an encoded `u8`.
+pub const MAX_LEN_U8: usize = 2;
+
+/// The maximum length of an encoded `u16`.
+pub const MAX_LEN_U16: usize = 3;
+
+/// The maximum length of an encoded `char`.
+pub const MAX_LEN_CHAR: usize = 3;
+
+/// The maximum length of an encoded `u32`.
+pub const MAX_LEN_U32: usize = 5;
+
+/// The maximum length of an encoded `u64`.
+pub const MAX_LEN_U64: usize = 9;
+
+/// The maximum length of an encoded `u128`.
+pub const MAX_LEN_U128: usize = 17;
+
+/// A buffer large enough to hold any encoded `u8`.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf: vu128::BufU8 = [0; vu128::MAX_LEN_U8];
+/// let encoded_len = vu128::encode_u8(&mut buf, u8::MAX);
+/// assert_eq!(encoded_len, vu128::MAX_LEN_U8);
+/// ```
+pub type BufU8 = [u8; MAX_LEN_U8];
+
+/// A buffer large enough to hold any encoded `u16`.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf: vu128::BufU16 = [0; vu128::MAX_LEN_U16];
+/// let encoded_len = vu128::encode_u16(&mut buf, u16::MAX);
+/// assert_eq!(encoded_len, vu128::MAX_LEN_U16);
+/// ```
+pub type BufU16 = [u8; MAX_LEN_U16];
+
+/// A buffer large enough to hold any encoded `char`.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf: vu128::BufChar = [0; vu128::MAX_LEN_CHAR];
+/// let encoded_len = vu128::encode_char(&mut buf, char::MAX);
+/// assert_eq!(encoded_len, vu128::MAX_LEN_CHAR);
+/// ```
+pub type BufChar = [u8; MAX_LEN_CHAR];
+
+/// A buffer large enough to hold any encoded `u32`.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf: vu128::BufU32 = [0; vu128::MAX_LEN_U32];
+/// let encoded_len = vu128::encode_u32(&mut buf, u32::MAX);
+/// assert_eq!(encoded_len, vu128::MAX_LEN_U32);
+/// ```
+pub type BufU32 = [u8; MAX_LEN_U32];
+
+/// A buffer large enough to hold any encoded `u64`.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf: vu128::BufU64 = [0; vu128::MAX_LEN_U64];
+/// let encoded_len = vu128::encode_u64(&mut buf, u64::MAX);
+/// assert_eq!(encoded_len, vu128::MAX_LEN_U64);
+/// ```
+pub type BufU64 = [u8; MAX_LEN_U64];
+
+/// A buffer large enough to hold any encoded `u128`.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf: vu128::BufU128 = [0; vu128::MAX_LEN_U128];
+/// let encoded_len = vu128::encode_u128(&mut buf, u128::MAX);
+/// assert_eq!(encoded_len, vu128::MAX_LEN_U128);
+/// ```
+pub type BufU128 = [u8; MAX_LEN_U128];
 
 /// Returns the encoded length in a `vu128` prefix byte.
 ///
@@ -122,465 +247,5645 @@ pub const fn encoded_len(b: u8) -> usize {
 	((b & 0x0F) + 2) as usize
 }
 
-/// Encodes a `u32` into a buffer, returning the encoded length.
+/// Returns the length of a `u8` encoded by [`encode_u8`].
 ///
-/// The contents of the buffer beyond the returned length are unspecified.
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 2];
+/// let encoded_len = vu128::encode_u8(&mut buf, 200);
+/// assert_eq!(vu128::encoded_len_u8(200), encoded_len);
+/// ```
+#[must_use]
+pub const fn encoded_len_u8(value: u8) -> usize {
+	if value < 0x80 {
+		return 1;
+	}
+	2
+}
+
+/// Returns the length of a `u16` encoded by [`encode_u16`].
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 3];
+/// let encoded_len = vu128::encode_u16(&mut buf, 12345);
+/// assert_eq!(vu128::encoded_len_u16(12345), encoded_len);
+/// ```
+#[must_use]
+pub const fn encoded_len_u16(value: u16) -> usize {
+	if value < 0x80 {
+		return 1;
+	}
+	if value < 0x4000 {
+		return 2;
+	}
+	3
+}
+
+/// Returns the length of a `u32` encoded by [`encode_u32`].
 ///
 /// # Examples
 ///
 /// ```
 /// let mut buf = [0u8; 5];
 /// let encoded_len = vu128::encode_u32(&mut buf, 12345);
-/// assert_eq!(&buf[..encoded_len], &[0xB9, 0xC0]);
+/// assert_eq!(vu128::encoded_len_u32(12345), encoded_len);
 /// ```
-#[inline]
 #[must_use]
-pub fn encode_u32(buf: &mut [u8; 5], value: u32) -> usize {
-	let mut x = value;
-	if x < 0x80 {
-		buf[0] = x as u8;
+pub const fn encoded_len_u32(value: u32) -> usize {
+	if value < 0x80 {
 		return 1;
 	}
-	if x < 0x10000000 {
-		if x < 0x00004000 {
-			x <<= 2;
-			buf[0] = 0x80 | ((x as u8) >> 2);
-			buf[1] = (x >> 8) as u8;
-			return 2;
-		}
-		if x < 0x00200000 {
-			x <<= 3;
-			buf[0] = 0xC0 | ((x as u8) >> 3);
-			buf[1] = (x >> 8) as u8;
-			buf[2] = (x >> 16) as u8;
-			return 3;
-		}
-		x <<= 4;
-		buf[0] = 0xE0 | ((x as u8) >> 4);
-		buf[1] = (x >> 8) as u8;
-		buf[2] = (x >> 16) as u8;
-		buf[3] = (x >> 24) as u8;
-		return 4;
+	if value < 0x00004000 {
+		return 2;
 	}
-
-	// SAFETY: buf has a const length of `size_of::<u32>() + 1`.
-	unsafe {
-		ptr_from_mut::<[u8; mem::size_of::<u32>() + 1]>(buf)
-			.cast::<u8>()
-			.add(1)
-			.cast::<u32>()
-			.write_unaligned(x.to_le());
+	if value < 0x00200000 {
+		return 3;
+	}
+	if value < 0x10000000 {
+		return 4;
 	}
-
-	buf[0] = 0xF3;
 	5
 }
 
-/// Encodes a `u64` into a buffer, returning the encoded length.
-///
-/// The contents of the buffer beyond the returned length are unspecified.
+/// Returns the length of a `u64` encoded by [`encode_u64`].
 ///
 /// # Examples
 ///
 /// ```
 /// let mut buf = [0u8; 9];
 /// let encoded_len = vu128::encode_u64(&mut buf, 12345);
-/// assert_eq!(&buf[..encoded_len], &[0xB9, 0xC0]);
+/// assert_eq!(vu128::encoded_len_u64(12345), encoded_len);
 /// ```
-#[inline]
 #[must_use]
-pub fn encode_u64(buf: &mut [u8; 9], value: u64) -> usize {
-	let mut x = value;
-	if x < 0x80 {
-		buf[0] = x as u8;
-		return 1;
-	}
-	if x < 0x10000000 {
-		if x < 0x00004000 {
-			x <<= 2;
-			buf[0] = 0x80 | ((x as u8) >> 2);
-			buf[1] = (x >> 8) as u8;
-			return 2;
-		}
-		if x < 0x00200000 {
-			x <<= 3;
-			buf[0] = 0xC0 | ((x as u8) >> 3);
-			buf[1] = (x >> 8) as u8;
-			buf[2] = (x >> 16) as u8;
-			return 3;
-		}
-		x <<= 4;
-		buf[0] = 0xE0 | ((x as u8) >> 4);
-		buf[1] = (x >> 8) as u8;
-		buf[2] = (x >> 16) as u8;
-		buf[3] = (x >> 24) as u8;
-		return 4;
-	}
-
-	// SAFETY: buf has a const length of `size_of::<u64>() + 1`.
-	unsafe {
-		ptr_from_mut::<[u8; mem::size_of::<u64>() + 1]>(buf)
-			.cast::<u8>()
-			.add(1)
-			.cast::<u64>()
-			.write_unaligned(x.to_le());
+pub const fn encoded_len_u64(value: u64) -> usize {
+	if value < 0x10000000 {
+		return encoded_len_u32(value as u32);
 	}
-
-	const LEN_MASK: u8 = 0b111;
-	let len = ((x.leading_zeros() >> 3) as u8) ^ LEN_MASK;
-	buf[0] = 0xF0 | len;
-	(len + 2) as usize
+	9 - (value.leading_zeros() >> 3) as usize
 }
 
-/// Encodes a `u128` into a buffer, returning the encoded length.
-///
-/// The contents of the buffer beyond the returned length are unspecified.
+/// Returns the length of a `u128` encoded by [`encode_u128`].
 ///
 /// # Examples
 ///
 /// ```
 /// let mut buf = [0u8; 17];
 /// let encoded_len = vu128::encode_u128(&mut buf, 12345);
-/// assert_eq!(&buf[..encoded_len], &[0xB9, 0xC0]);
+/// assert_eq!(vu128::encoded_len_u128(12345), encoded_len);
 /// ```
-#[inline]
 #[must_use]
-pub fn encode_u128(buf: &mut [u8; 17], value: u128) -> usize {
-	if value < 0x80 {
-		buf[0] = value as u8;
-		return 1;
-	}
+pub const fn encoded_len_u128(value: u128) -> usize {
 	if value < 0x10000000 {
-		// SAFETY: A `[u8; 17]` can be safely truncated to a `[u8; 5]`.
-		let buf_u32 = unsafe {
-			&mut *(ptr_from_mut::<[u8; 17]>(buf).cast::<[u8; 5]>())
-		};
-		return encode_u32(buf_u32, value as u32);
-	}
-
-	// SAFETY: buf has a const length of `size_of::<u128>() + 1`.
-	unsafe {
-		ptr_from_mut::<[u8; mem::size_of::<u128>() + 1]>(buf)
-			.cast::<u8>()
-			.add(1)
-			.cast::<u128>()
-			.write_unaligned(value.to_le());
+		return encoded_len_u32(value as u32);
 	}
-
-	const LEN_MASK: u8 = 0b1111;
-	let len = ((value.leading_zeros() >> 3) as u8) ^ LEN_MASK;
-	buf[0] = 0xF0 | len;
-	(len + 2) as usize
+	17 - (value.leading_zeros() >> 3) as usize
 }
 
-/// Decodes a `u32` from a buffer, returning the value and encoded length.
+/// Encodes a `u8` into a buffer, returning the encoded length.
+///
+/// The contents of the buffer beyond the returned length are unspecified.
 ///
 /// # Examples
 ///
 /// ```
-/// let mut buf = [0u8; 5];
-/// let encoded_len = vu128::encode_u32(&mut buf, 123);
-/// assert_eq!(vu128::decode_u32(&buf), (123, encoded_len));
+/// let mut buf = [0u8; 2];
+/// let encoded_len = vu128::encode_u8(&mut buf, 200);
+/// assert_eq!(&buf[..encoded_len], &[0x88, 0x03]);
 /// ```
 #[inline]
 #[must_use]
-pub fn decode_u32(buf: &[u8; 5]) -> (u32, usize) {
-	let buf0 = buf[0] as u32;
-	if (buf0 & 0x80) == 0 {
-		return (buf0, 1);
-	}
-	if (buf0 & 0b01000000) == 0 {
-		let low = (buf0 as u8) & 0x3F;
-		let value = ((buf[1] as u32) << 6) | (low as u32);
-		return (value, 2);
-	}
-	if buf0 >= 0xF0 {
-		let len = ((buf0 as u8) & 0x0F) + 2;
-		let value = ((buf[4] as u32) << 24)
-		          | ((buf[3] as u32) << 16)
-		          | ((buf[2] as u32) << 8)
-		          | (buf[1] as u32);
-		return (value, len as usize);
-	}
-	if (buf0 & 0b00100000) == 0 {
-		let low = (buf0 as u8) & 0x1F;
-		let value = ((buf[2] as u32) << 13)
-		          | ((buf[1] as u32) << 5)
-		          | (low as u32);
-		return (value, 3);
-	}
-	let value = ((buf[3] as u32) << 20)
-	          | ((buf[2] as u32) << 12)
-	          | ((buf[1] as u32) << 4)
-	          | (buf0 & 0x0F);
-	(value, 4)
+pub fn encode_u8(buf: &mut BufU8, value: u8) -> usize {
+	encode_u8_raw(as_uninit_mut(buf), value)
 }
 
-/// Decodes a `u64` from a buffer, returning the value and encoded length.
+/// Encodes a `u16` into a buffer, returning the encoded length.
+///
+/// The contents of the buffer beyond the returned length are unspecified.
 ///
 /// # Examples
 ///
 /// ```
-/// let mut buf = [0u8; 9];
-/// let encoded_len = vu128::encode_u64(&mut buf, 123);
-/// assert_eq!(vu128::decode_u64(&buf), (123, encoded_len));
+/// let mut buf = [0u8; 3];
+/// let encoded_len = vu128::encode_u16(&mut buf, 12345);
+/// assert_eq!(&buf[..encoded_len], &[0xB9, 0xC0]);
 /// ```
 #[inline]
 #[must_use]
-pub fn decode_u64(buf: &[u8; 9]) -> (u64, usize) {
-	let buf0 = buf[0] as u64;
-	if (buf0 & 0x80) == 0 {
-		return (buf0, 1);
-	}
-	if buf0 < 0xF0 {
-		if (buf0 & 0b01000000) == 0 {
-			let low = (buf0 as u8) & 0b00111111;
-			let value = ((buf[1] as u32) << 6) | (low as u32);
-			return (value as u64, 2);
-		}
-		if (buf0 & 0b00100000) == 0 {
-			let low = (buf0 as u8) & 0b00011111;
-			let value = ((buf[2] as u32) << 13)
-			          | ((buf[1] as u32) << 5)
-			          | (low as u32);
-			return (value as u64, 3);
-		}
-		let value = ((buf[3] as u32) << 20)
-		          | ((buf[2] as u32) << 12)
-		          | ((buf[1] as u32) << 4)
-		          | ((buf0 as u32) & 0b00001111);
-		return (value as u64, 4);
-	}
-
-	// SAFETY: buf has a const length of `size_of::<u64>() + 1`.
-	let value = u64::from_le(unsafe {
-		ptr_from_ref::<[u8; mem::size_of::<u64>() + 1]>(buf)
-			.cast::<u8>()
-			.add(1)
-			.cast::<u64>()
-			.read_unaligned()
-	});
-
-	const LEN_MASK: u8 = 0b111;
-	let len = buf[0] & 0x0F;
-	let mask_octets = (len & LEN_MASK) ^ LEN_MASK;
-	let mask = u64::MAX >> (mask_octets * 8);
-	(value & mask, (len + 2) as usize)
+pub fn encode_u16(buf: &mut BufU16, value: u16) -> usize {
+	encode_u16_raw(as_uninit_mut(buf), value)
 }
 
-/// Decodes a `u128` from a buffer, returning the value and encoded length.
+/// Encodes a `u32` into a buffer, returning the encoded length.
+///
+/// The contents of the buffer beyond the returned length are unspecified.
 ///
 /// # Examples
 ///
 /// ```
-/// let mut buf = [0u8; 17];
-/// let encoded_len = vu128::encode_u128(&mut buf, 123);
-/// assert_eq!(vu128::decode_u128(&buf), (123, encoded_len));
+/// let mut buf = [0u8; 5];
+/// let encoded_len = vu128::encode_u32(&mut buf, 12345);
+/// assert_eq!(&buf[..encoded_len], &[0xB9, 0xC0]);
 /// ```
 #[inline]
 #[must_use]
-pub fn decode_u128(buf: &[u8; 17]) -> (u128, usize) {
-	if (buf[0] & 0x80) == 0 {
-		return (buf[0] as u128, 1);
-	}
-	if buf[0] < 0xF0 {
-		// SAFETY: A `[u8; 17]` can be safely truncated to a `[u8; 5]`.
-		let buf_u32 = unsafe {
-			&*(ptr_from_ref::<[u8; 17]>(buf).cast::<[u8; 5]>())
-		};
-		let (value, len) = decode_u32(buf_u32);
-		return (value as u128, len);
-	}
+pub fn encode_u32(buf: &mut BufU32, value: u32) -> usize {
+	encode_u32_raw(as_uninit_mut(buf), value)
+}
 
-	// SAFETY: buf has a const length of `size_of::<u128>() + 1`.
-	let value = u128::from_le(unsafe {
-		ptr_from_ref::<[u8; mem::size_of::<u128>() + 1]>(buf)
-			.cast::<u8>()
-			.add(1)
-			.cast::<u128>()
-			.read_unaligned()
-	});
-	const LEN_MASK: u8 = 0b1111;
-	let len = buf[0] & 0x0F;
-	let mask_octets = (len & LEN_MASK) ^ LEN_MASK;
-	let mask = u128::MAX >> (mask_octets * 8);
-	(value & mask, (len + 2) as usize)
+/// Encodes a `u64` into a buffer, returning the encoded length.
+///
+/// The contents of the buffer beyond the returned length are unspecified.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 9];
+/// let encoded_len = vu128::encode_u64(&mut buf, 12345);
+/// assert_eq!(&buf[..encoded_len], &[0xB9, 0xC0]);
+/// ```
+#[inline]
+#[must_use]
+pub fn encode_u64(buf: &mut BufU64, value: u64) -> usize {
+	encode_u64_raw(as_uninit_mut(buf), value)
 }
 
-macro_rules! encode_iNN {
-	($(#[$docs:meta])* $name:ident ( $it:ident, $ut:ident, $encode_fn:ident ) ) => {
-		$(#[$docs])*
-		#[inline]
-		#[must_use]
-		pub fn $name(buf: &mut [u8; mem::size_of::<$ut>()+1], value: $it) -> usize {
-			const ZIGZAG_SHIFT: u8 = ($ut::BITS as u8) - 1;
-			let zigzag = ((value >> ZIGZAG_SHIFT) as $ut) ^ ((value << 1) as $ut);
-			$encode_fn(buf, zigzag)
-		}
-	};
+/// Encodes a `u128` into a buffer, returning the encoded length.
+///
+/// The contents of the buffer beyond the returned length are unspecified.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 17];
+/// let encoded_len = vu128::encode_u128(&mut buf, 12345);
+/// assert_eq!(&buf[..encoded_len], &[0xB9, 0xC0]);
+/// ```
+#[inline]
+#[must_use]
+pub fn encode_u128(buf: &mut BufU128, value: u128) -> usize {
+	encode_u128_raw(as_uninit_mut(buf), value)
 }
 
-macro_rules! decode_iNN {
-	($(#[$docs:meta])* $name:ident ( $it:ident, $ut:ident, $decode_fn:ident ) ) => {
-		$(#[$docs])*
-		#[inline]
-		#[must_use]
-		pub fn $name(buf: &[u8; mem::size_of::<$ut>()+1]) -> ($it, usize) {
-			let (zz, len) = $decode_fn(buf);
-			let value = ((zz >> 1) as $it) ^ (-((zz & 1) as $it));
-			(value, len)
-		}
-	};
+/// Encodes a `u32` into a buffer using exactly `len` bytes.
+///
+/// The value is zero-padded to fill the requested length, producing an
+/// over-long encoding if `len` is greater than the length used by
+/// [`encode_u32`]. This allows space for a value to be reserved before the
+/// value is known. The contents of the buffer beyond `len` are unspecified.
+///
+/// An error is returned if `len` is zero or greater than 5, or if the value
+/// does not fit in `len` bytes.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 5];
+/// vu128::encode_u32_padded(&mut buf, 12345, 4).unwrap();
+/// assert_eq!(&buf[..4], &[0xE9, 0x03, 0x03, 0x00]);
+/// assert_eq!(vu128::decode_u32(&buf), (12345, 4));
+/// ```
+#[inline]
+pub fn encode_u32_padded(
+	buf: &mut BufU32,
+	value: u32,
+	len: usize,
+) -> Result<(), EncodeError> {
+	encode_padded(buf, value.into(), len)
 }
 
-encode_iNN! {
-	/// Encodes an `i32` into a buffer, returning the encoded length.
-	///
-	/// The contents of the buffer beyond the returned length are unspecified.
-	///
-	/// # Examples
-	///
-	/// ```
-	/// let mut buf = [0u8; 5];
-	/// let encoded_len = vu128::encode_i32(&mut buf, 123);
-	/// assert_eq!(&buf[..encoded_len], &[0xB6, 0x03]);
-	/// ```
-	encode_i32(i32, u32, encode_u32)
+/// Encodes a `u64` into a buffer using exactly `len` bytes.
+///
+/// The value is zero-padded to fill the requested length, producing an
+/// over-long encoding if `len` is greater than the length used by
+/// [`encode_u64`]. This allows space for a value to be reserved before the
+/// value is known. The contents of the buffer beyond `len` are unspecified.
+///
+/// An error is returned if `len` is zero or greater than 9, or if the value
+/// does not fit in `len` bytes.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 9];
+/// vu128::encode_u64_padded(&mut buf, 12345, 4).unwrap();
+/// assert_eq!(&buf[..4], &[0xE9, 0x03, 0x03, 0x00]);
+/// assert_eq!(vu128::decode_u64(&buf), (12345, 4));
+/// ```
+#[inline]
+pub fn encode_u64_padded(
+	buf: &mut BufU64,
+	value: u64,
+	len: usize,
+) -> Result<(), EncodeError> {
+	encode_padded(buf, value.into(), len)
 }
 
-encode_iNN! {
-	/// Encodes an `i64` into a buffer, returning the encoded length.
-	///
-	/// The contents of the buffer beyond the returned length are unspecified.
-	///
-	/// # Examples
-	///
-	/// ```
-	/// let mut buf = [0u8; 9];
-	/// let encoded_len = vu128::encode_i64(&mut buf, 123);
-	/// assert_eq!(&buf[..encoded_len], &[0xB6, 0x03]);
-	/// ```
-	encode_i64(i64, u64, encode_u64)
+/// Encodes a `u128` into a buffer using exactly `len` bytes.
+///
+/// The value is zero-padded to fill the requested length, producing an
+/// over-long encoding if `len` is greater than the length used by
+/// [`encode_u128`]. This allows space for a value to be reserved before the
+/// value is known. The contents of the buffer beyond `len` are unspecified.
+///
+/// An error is returned if `len` is zero or greater than 17, or if the value
+/// does not fit in `len` bytes.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 17];
+/// vu128::encode_u128_padded(&mut buf, 12345, 4).unwrap();
+/// assert_eq!(&buf[..4], &[0xE9, 0x03, 0x03, 0x00]);
+/// assert_eq!(vu128::decode_u128(&buf), (12345, 4));
+/// ```
+#[inline]
+pub fn encode_u128_padded(
+	buf: &mut BufU128,
+	value: u128,
+	len: usize,
+) -> Result<(), EncodeError> {
+	encode_padded(buf, value, len)
 }
 
-encode_iNN! {
-	/// Encodes an `i128` into a buffer, returning the encoded length.
-	///
-	/// The contents of the buffer beyond the returned length are unspecified.
-	///
-	/// # Examples
-	///
-	/// ```
-	/// let mut buf = [0u8; 17];
-	/// let encoded_len = vu128::encode_i128(&mut buf, 123);
-	/// assert_eq!(&buf[..encoded_len], &[0xB6, 0x03]);
-	/// ```
-	encode_i128(i128, u128, encode_u128)
+/// Encodes a `u8` into an uninitialized buffer, returning the encoded
+/// bytes.
+///
+/// Only the returned prefix of the buffer is initialized.
+///
+/// # Examples
+///
+/// ```
+/// # use core::mem::MaybeUninit;
+/// let mut buf = [MaybeUninit::uninit(); vu128::MAX_LEN_U8];
+/// let encoded = vu128::encode_u8_uninit(&mut buf, 123);
+/// assert_eq!(encoded, &[0x7B]);
+/// ```
+#[inline]
+pub fn encode_u8_uninit(
+	buf: &mut [MaybeUninit<u8>; MAX_LEN_U8],
+	value: u8,
+) -> &[u8] {
+	let len = encode_u8_raw(buf, value);
+	// SAFETY: The first `len` bytes of `buf` were initialized by the encoder.
+	unsafe { assume_init_prefix(buf, len) }
 }
 
-decode_iNN! {
-	/// Decodes an `i32` from a buffer, returning the value and encoded length.
-	///
-	/// # Examples
-	///
-	/// ```
-	/// let mut buf = [0u8; 5];
-	/// let encoded_len = vu128::encode_i32(&mut buf, 123);
-	/// assert_eq!(vu128::decode_i32(&buf), (123, encoded_len));
-	/// ```
-	decode_i32(i32, u32, decode_u32)
+/// Encodes a `u16` into an uninitialized buffer, returning the encoded
+/// bytes.
+///
+/// Only the returned prefix of the buffer is initialized.
+///
+/// # Examples
+///
+/// ```
+/// # use core::mem::MaybeUninit;
+/// let mut buf = [MaybeUninit::uninit(); vu128::MAX_LEN_U16];
+/// let encoded = vu128::encode_u16_uninit(&mut buf, 123);
+/// assert_eq!(encoded, &[0x7B]);
+/// ```
+#[inline]
+pub fn encode_u16_uninit(
+	buf: &mut [MaybeUninit<u8>; MAX_LEN_U16],
+	value: u16,
+) -> &[u8] {
+	let len = encode_u16_raw(buf, value);
+	// SAFETY: The first `len` bytes of `buf` were initialized by the encoder.
+	unsafe { assume_init_prefix(buf, len) }
 }
 
-decode_iNN! {
-	/// Decodes an `i64` from a buffer, returning the value and encoded length.
-	///
-	/// # Examples
-	///
-	/// ```
-	/// let mut buf = [0u8; 9];
-	/// let encoded_len = vu128::encode_i64(&mut buf, 123);
-	/// assert_eq!(vu128::decode_i64(&buf), (123, encoded_len));
-	/// ```
-	decode_i64(i64, u64, decode_u64)
+/// Encodes a `u32` into an uninitialized buffer, returning the encoded
+/// bytes.
+///
+/// Only the returned prefix of the buffer is initialized.
+///
+/// # Examples
+///
+/// ```
+/// # use core::mem::MaybeUninit;
+/// let mut buf = [MaybeUninit::uninit(); vu128::MAX_LEN_U32];
+/// let encoded = vu128::encode_u32_uninit(&mut buf, 123);
+/// assert_eq!(encoded, &[0x7B]);
+/// ```
+#[inline]
+pub fn encode_u32_uninit(
+	buf: &mut [MaybeUninit<u8>; MAX_LEN_U32],
+	value: u32,
+) -> &[u8] {
+	let len = encode_u32_raw(buf, value);
+	// SAFETY: The first `len` bytes of `buf` were initialized by the encoder.
+	unsafe { assume_init_prefix(buf, len) }
 }
 
-decode_iNN! {
-	/// Decodes an `i128` from a buffer, returning the value and encoded length.
-	///
-	/// # Examples
-	///
-	/// ```
-	/// let mut buf = [0u8; 17];
-	/// let encoded_len = vu128::encode_i128(&mut buf, 123);
-	/// assert_eq!(vu128::decode_i128(&buf), (123, encoded_len));
-	/// ```
-	decode_i128(i128, u128, decode_u128)
+/// Encodes a `u64` into an uninitialized buffer, returning the encoded
+/// bytes.
+///
+/// Only the returned prefix of the buffer is initialized.
+///
+/// # Examples
+///
+/// ```
+/// # use core::mem::MaybeUninit;
+/// let mut buf = [MaybeUninit::uninit(); vu128::MAX_LEN_U64];
+/// let encoded = vu128::encode_u64_uninit(&mut buf, 123);
+/// assert_eq!(encoded, &[0x7B]);
+/// ```
+#[inline]
+pub fn encode_u64_uninit(
+	buf: &mut [MaybeUninit<u8>; MAX_LEN_U64],
+	value: u64,
+) -> &[u8] {
+	let len = encode_u64_raw(buf, value);
+	// SAFETY: The first `len` bytes of `buf` were initialized by the encoder.
+	unsafe { assume_init_prefix(buf, len) }
 }
 
-/// Encodes an `f32` into a buffer, returning the encoded length.
+/// Encodes a `u128` into an uninitialized buffer, returning the encoded
+/// bytes.
 ///
-/// The contents of the buffer beyond the returned length are unspecified.
+/// Only the returned prefix of the buffer is initialized.
 ///
 /// # Examples
 ///
 /// ```
-/// let mut buf = [0u8; 5];
-/// let encoded_len = vu128::encode_f32(&mut buf, 2.5);
-/// assert_eq!(&buf[..encoded_len], &[0x80, 0x81]);
+/// # use core::mem::MaybeUninit;
+/// let mut buf = [MaybeUninit::uninit(); vu128::MAX_LEN_U128];
+/// let encoded = vu128::encode_u128_uninit(&mut buf, 123);
+/// assert_eq!(encoded, &[0x7B]);
+/// ```
+#[inline]
+pub fn encode_u128_uninit(
+	buf: &mut [MaybeUninit<u8>; MAX_LEN_U128],
+	value: u128,
+) -> &[u8] {
+	let len = encode_u128_raw(buf, value);
+	// SAFETY: The first `len` bytes of `buf` were initialized by the encoder.
+	unsafe { assume_init_prefix(buf, len) }
+}
+
+/// Decodes a `u8` from a buffer, returning the value and encoded length.
+///
+/// If the encoded value does not fit in a `u8`, the returned value is
+/// unspecified. Use [`decode_u8_checked`] to detect such values.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 2];
+/// let encoded_len = vu128::encode_u8(&mut buf, 123);
+/// assert_eq!(vu128::decode_u8(&buf), (123, encoded_len));
 /// ```
 #[inline]
 #[must_use]
-pub fn encode_f32(buf: &mut [u8; 5], value: f32) -> usize {
-	encode_u32(buf, value.to_bits().swap_bytes())
+pub fn decode_u8(buf: &BufU8) -> (u8, usize) {
+	let (value, len) = decode_u32(&widen_buf(buf));
+	(value as u8, len)
 }
 
-/// Encodes an `f64` into a buffer, returning the encoded length.
+/// Decodes a `u16` from a buffer, returning the value and encoded length.
 ///
-/// The contents of the buffer beyond the returned length are unspecified.
+/// If the encoded value does not fit in a `u16`, the returned value is
+/// unspecified. Use [`decode_u16_checked`] to detect such values.
 ///
 /// # Examples
 ///
 /// ```
-/// let mut buf = [0u8; 9];
-/// let encoded_len = vu128::encode_f64(&mut buf, 2.5);
-/// assert_eq!(&buf[..encoded_len], &[0x80, 0x11]);
+/// let mut buf = [0u8; 3];
+/// let encoded_len = vu128::encode_u16(&mut buf, 123);
+/// assert_eq!(vu128::decode_u16(&buf), (123, encoded_len));
 /// ```
 #[inline]
 #[must_use]
-pub fn encode_f64(buf: &mut [u8; 9], value: f64) -> usize {
-	encode_u64(buf, value.to_bits().swap_bytes())
+pub fn decode_u16(buf: &BufU16) -> (u16, usize) {
+	let (value, len) = decode_u32(&widen_buf(buf));
+	(value as u16, len)
 }
 
-/// Decodes an `f32` from a buffer, returning the value and encoded length.
+/// Decodes a `u32` from a buffer, returning the value and encoded length.
+///
+/// If the prefix byte indicates an encoded length greater than the buffer,
+/// the returned value is unspecified. Use [`decode_u32_checked`] to detect
+/// such values.
 ///
 /// # Examples
 ///
 /// ```
 /// let mut buf = [0u8; 5];
-/// let encoded_len = vu128::encode_f32(&mut buf, 2.5);
-/// assert_eq!(vu128::decode_f32(&buf), (2.5, encoded_len));
+/// let encoded_len = vu128::encode_u32(&mut buf, 123);
+/// assert_eq!(vu128::decode_u32(&buf), (123, encoded_len));
 /// ```
 #[inline]
 #[must_use]
-pub fn decode_f32(buf: &[u8; 5]) -> (f32, usize) {
-	let (swapped, len) = decode_u32(buf);
-	(f32::from_bits(swapped.swap_bytes()), len)
+pub fn decode_u32(buf: &BufU32) -> (u32, usize) {
+	let buf0 = buf[0] as u32;
+	if (buf0 & 0x80) == 0 {
+		return (buf0, 1);
+	}
+	if (buf0 & 0b01000000) == 0 {
+		let low = (buf0 as u8) & 0x3F;
+		let value = ((buf[1] as u32) << 6) | (low as u32);
+		return (value, 2);
+	}
+	if buf0 >= 0xF0 {
+		let len = ((buf0 as u8) & 0x0F) + 2;
+		let value = ((buf[4] as u32) << 24)
+		          | ((buf[3] as u32) << 16)
+		          | ((buf[2] as u32) << 8)
+		          | (buf[1] as u32);
+		return (value, len as usize);
+	}
+	if (buf0 & 0b00100000) == 0 {
+		let low = (buf0 as u8) & 0x1F;
+		let value = ((buf[2] as u32) << 13)
+		          | ((buf[1] as u32) << 5)
+		          | (low as u32);
+		return (value, 3);
+	}
+	let value = ((buf[3] as u32) << 20)
+	          | ((buf[2] as u32) << 12)
+	          | ((buf[1] as u32) << 4)
+	          | (buf0 & 0x0F);
+	(value, 4)
 }
 
-/// Decodes an `f64` from a buffer, returning the value and encoded length.
+/// Decodes a `u64` from a buffer, returning the value and encoded length.
+///
+/// If the prefix byte indicates an encoded length greater than the buffer,
+/// the returned value is unspecified. Use [`decode_u64_checked`] to detect
+/// such values.
 ///
 /// # Examples
 ///
 /// ```
 /// let mut buf = [0u8; 9];
-/// let encoded_len = vu128::encode_f64(&mut buf, 2.5);
-/// assert_eq!(vu128::decode_f64(&buf), (2.5, encoded_len));
+/// let encoded_len = vu128::encode_u64(&mut buf, 123);
+/// assert_eq!(vu128::decode_u64(&buf), (123, encoded_len));
 /// ```
 #[inline]
 #[must_use]
-pub fn decode_f64(buf: &[u8; 9]) -> (f64, usize) {
-	let (swapped, len) = decode_u64(buf);
-	(f64::from_bits(swapped.swap_bytes()), len)
+pub fn decode_u64(buf: &BufU64) -> (u64, usize) {
+	let buf0 = buf[0] as u64;
+	if (buf0 & 0x80) == 0 {
+		return (buf0, 1);
+	}
+	if buf0 < 0xF0 {
+		if (buf0 & 0b01000000) == 0 {
+			let low = (buf0 as u8) & 0b00111111;
+			let value = ((buf[1] as u32) << 6) | (low as u32);
+			return (value as u64, 2);
+		}
+		if (buf0 & 0b00100000) == 0 {
+			let low = (buf0 as u8) & 0b00011111;
+			let value = ((buf[2] as u32) << 13)
+			          | ((buf[1] as u32) << 5)
+			          | (low as u32);
+			return (value as u64, 3);
+		}
+		let value = ((buf[3] as u32) << 20)
+		          | ((buf[2] as u32) << 12)
+		          | ((buf[1] as u32) << 4)
+		          | ((buf0 as u32) & 0b00001111);
+		return (value as u64, 4);
+	}
+
+	// SAFETY: buf has a const length of `size_of::<u64>() + 1`.
+	let value = u64::from_le(unsafe {
+		ptr_from_ref::<[u8; mem::size_of::<u64>() + 1]>(buf)
+			.cast::<u8>()
+			.add(1)
+			.cast::<u64>()
+			.read_unaligned()
+	});
+
+	const LEN_MASK: u8 = 0b111;
+	let len = buf[0] & 0x0F;
+	let mask_octets = (len & LEN_MASK) ^ LEN_MASK;
+	let mask = u64::MAX >> (mask_octets * 8);
+	(value & mask, (len + 2) as usize)
+}
+
+/// Decodes a `u128` from a buffer, returning the value and encoded length.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 17];
+/// let encoded_len = vu128::encode_u128(&mut buf, 123);
+/// assert_eq!(vu128::decode_u128(&buf), (123, encoded_len));
+/// ```
+#[inline]
+#[must_use]
+pub fn decode_u128(buf: &BufU128) -> (u128, usize) {
+	if (buf[0] & 0x80) == 0 {
+		return (buf[0] as u128, 1);
+	}
+	if buf[0] < 0xF0 {
+		// SAFETY: A `[u8; 17]` can be safely truncated to a `[u8; 5]`.
+		let buf_u32 = unsafe {
+			&*(ptr_from_ref::<BufU128>(buf).cast::<BufU32>())
+		};
+		let (value, len) = decode_u32(buf_u32);
+		return (value as u128, len);
+	}
+
+	// SAFETY: buf has a const length of `size_of::<u128>() + 1`.
+	let value = u128::from_le(unsafe {
+		ptr_from_ref::<[u8; mem::size_of::<u128>() + 1]>(buf)
+			.cast::<u8>()
+			.add(1)
+			.cast::<u128>()
+			.read_unaligned()
+	});
+	const LEN_MASK: u8 = 0b1111;
+	let len = buf[0] & 0x0F;
+	let mask_octets = (len & LEN_MASK) ^ LEN_MASK;
+	let mask = u128::MAX >> (mask_octets * 8);
+	(value & mask, (len + 2) as usize)
+}
+
+/// Decodes a `u8` from a buffer, returning the value and encoded length.
+///
+/// Unlike [`decode_u8`], an error is returned if the encoded value does not
+/// fit in a `u8`.
+///
+/// # Examples
+///
+/// ```
+/// # use vu128::DecodeErrorKind;
+/// let mut buf = [0u8; 2];
+/// let encoded_len = vu128::encode_u8(&mut buf, 123);
+/// assert_eq!(vu128::decode_u8_checked(&buf), Ok((123, encoded_len)));
+///
+/// let err = vu128::decode_u8_checked(&[0x80, 0x04]).unwrap_err();
+/// assert_eq!(err.kind(), DecodeErrorKind::OutOfRange);
+/// ```
+#[inline]
+pub fn decode_u8_checked(buf: &BufU8) -> Result<(u8, usize), DecodeError> {
+	check_encoded_len(buf[0], MAX_LEN_U8)?;
+	let (value, len) = decode_u32(&widen_buf(buf));
+	narrow_decoded(value, len)
+}
+
+/// Decodes a `u16` from a buffer, returning the value and encoded length.
+///
+/// Unlike [`decode_u16`], an error is returned if the encoded value does not
+/// fit in a `u16`.
+///
+/// # Examples
+///
+/// ```
+/// # use vu128::DecodeErrorKind;
+/// let mut buf = [0u8; 3];
+/// let encoded_len = vu128::encode_u16(&mut buf, 123);
+/// assert_eq!(vu128::decode_u16_checked(&buf), Ok((123, encoded_len)));
+///
+/// let err = vu128::decode_u16_checked(&[0xC0, 0x00, 0x10]).unwrap_err();
+/// assert_eq!(err.kind(), DecodeErrorKind::OutOfRange);
+/// ```
+#[inline]
+pub fn decode_u16_checked(buf: &BufU16) -> Result<(u16, usize), DecodeError> {
+	check_encoded_len(buf[0], MAX_LEN_U16)?;
+	let (value, len) = decode_u32(&widen_buf(buf));
+	narrow_decoded(value, len)
+}
+
+/// Decodes a `u32` from a buffer, returning the value and encoded length.
+///
+/// Unlike [`decode_u32`], an error is returned if the prefix byte indicates an
+/// encoded length greater than the buffer, such as for a `u64` value that
+/// does not fit in a `u32`.
+///
+/// # Examples
+///
+/// ```
+/// # use vu128::DecodeErrorKind;
+/// let mut buf = [0u8; 5];
+/// let encoded_len = vu128::encode_u32(&mut buf, 123);
+/// assert_eq!(vu128::decode_u32_checked(&buf), Ok((123, encoded_len)));
+///
+/// let buf = [0xF4, 0x00, 0x00, 0x00, 0x00];
+/// let err = vu128::decode_u32_checked(&buf).unwrap_err();
+/// let expect = DecodeErrorKind::Overflow { encoded_len: 6, max_len: 5 };
+/// assert_eq!(err.kind(), expect);
+/// ```
+#[inline]
+pub fn decode_u32_checked(buf: &BufU32) -> Result<(u32, usize), DecodeError> {
+	check_encoded_len(buf[0], MAX_LEN_U32)?;
+	Ok(decode_u32_exact(buf))
+}
+
+/// Decodes a `u64` from a buffer, returning the value and encoded length.
+///
+/// Unlike [`decode_u64`], an error is returned if the prefix byte indicates an
+/// encoded length greater than the buffer, such as for a `u128` value that
+/// does not fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// # use vu128::DecodeErrorKind;
+/// let mut buf = [0u8; 9];
+/// let encoded_len = vu128::encode_u64(&mut buf, 123);
+/// assert_eq!(vu128::decode_u64_checked(&buf), Ok((123, encoded_len)));
+///
+/// let buf = [0xFF, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+/// let err = vu128::decode_u64_checked(&buf).unwrap_err();
+/// let expect = DecodeErrorKind::Overflow { encoded_len: 17, max_len: 9 };
+/// assert_eq!(err.kind(), expect);
+/// ```
+#[inline]
+pub fn decode_u64_checked(buf: &BufU64) -> Result<(u64, usize), DecodeError> {
+	check_encoded_len(buf[0], MAX_LEN_U64)?;
+	Ok(decode_u64(buf))
+}
+
+/// Decodes a `u8` from a slice, returning the value and encoded length.
+///
+/// Unlike [`decode_u8`], the input may be of any length. An error is
+/// returned if the slice is shorter than the encoded length indicated by its
+/// first byte, if that length is greater than [`MAX_LEN_U8`], or if the
+/// encoded value does not fit in a `u8`.
+///
+/// # Examples
+///
+/// ```
+/// let buf = [0x88, 0x03];
+/// assert_eq!(vu128::decode_u8_from_slice(&buf), Ok((200, 2)));
+/// assert!(vu128::decode_u8_from_slice(&buf[..1]).is_err());
+/// ```
+#[inline]
+pub fn decode_u8_from_slice(buf: &[u8]) -> Result<(u8, usize), DecodeError> {
+	let (value, len) =
+		decode_unsigned_from_slice(buf, MAX_LEN_U8, decode_u32_exact)?;
+	narrow_decoded(value, len)
+}
+
+/// Decodes a `u16` from a slice, returning the value and encoded length.
+///
+/// Unlike [`decode_u16`], the input may be of any length. An error is
+/// returned if the slice is shorter than the encoded length indicated by its
+/// first byte, if that length is greater than [`MAX_LEN_U16`], or if the
+/// encoded value does not fit in a `u16`.
+///
+/// # Examples
+///
+/// ```
+/// let buf = [0xB9, 0xC0];
+/// assert_eq!(vu128::decode_u16_from_slice(&buf), Ok((12345, 2)));
+/// assert!(vu128::decode_u16_from_slice(&buf[..1]).is_err());
+/// ```
+#[inline]
+pub fn decode_u16_from_slice(buf: &[u8]) -> Result<(u16, usize), DecodeError> {
+	let (value, len) =
+		decode_unsigned_from_slice(buf, MAX_LEN_U16, decode_u32_exact)?;
+	narrow_decoded(value, len)
+}
+
+/// Decodes a `u32` from a slice, returning the value and encoded length.
+///
+/// Unlike [`decode_u32`], the input may be of any length. An error is
+/// returned if the slice is shorter than the encoded length indicated by its
+/// first byte, or if that length is greater than [`MAX_LEN_U32`]. Over-long
+/// encodings are rejected even if their extra bytes are zero, matching
+/// [`decode_u32_checked`].
+///
+/// # Examples
+///
+/// ```
+/// # use vu128::DecodeErrorKind;
+/// let buf = [0xB9, 0xC0];
+/// assert_eq!(vu128::decode_u32_from_slice(&buf), Ok((12345, 2)));
+///
+/// let err = vu128::decode_u32_from_slice(&buf[..1]).unwrap_err();
+/// assert_eq!(err.kind(), DecodeErrorKind::Truncated { needed: 2, available: 1 });
+/// ```
+#[inline]
+pub fn decode_u32_from_slice(buf: &[u8]) -> Result<(u32, usize), DecodeError> {
+	decode_unsigned_from_slice(buf, MAX_LEN_U32, decode_u32_exact)
+}
+
+/// Decodes a `u64` from a slice, returning the value and encoded length.
+///
+/// Unlike [`decode_u64`], the input may be of any length. An error is
+/// returned if the slice is shorter than the encoded length indicated by its
+/// first byte, or if that length is greater than [`MAX_LEN_U64`]. Over-long
+/// encodings are rejected even if their extra bytes are zero, matching
+/// [`decode_u64_checked`].
+///
+/// # Examples
+///
+/// ```
+/// # use vu128::DecodeErrorKind;
+/// let buf = [0xB9, 0xC0];
+/// assert_eq!(vu128::decode_u64_from_slice(&buf), Ok((12345, 2)));
+///
+/// let err = vu128::decode_u64_from_slice(&buf[..1]).unwrap_err();
+/// assert_eq!(err.kind(), DecodeErrorKind::Truncated { needed: 2, available: 1 });
+/// ```
+#[inline]
+pub fn decode_u64_from_slice(buf: &[u8]) -> Result<(u64, usize), DecodeError> {
+	decode_unsigned_from_slice(buf, MAX_LEN_U64, decode_u64)
+}
+
+/// Decodes a `u128` from a slice, returning the value and encoded length.
+///
+/// Unlike [`decode_u128`], the input may be of any length. An error is
+/// returned if the slice is shorter than the encoded length indicated by its
+/// first byte.
+///
+/// # Examples
+///
+/// ```
+/// # use vu128::DecodeErrorKind;
+/// let buf = [0xB9, 0xC0];
+/// assert_eq!(vu128::decode_u128_from_slice(&buf), Ok((12345, 2)));
+///
+/// let err = vu128::decode_u128_from_slice(&buf[..1]).unwrap_err();
+/// assert_eq!(err.kind(), DecodeErrorKind::Truncated { needed: 2, available: 1 });
+/// ```
+#[inline]
+pub fn decode_u128_from_slice(
+	buf: &[u8],
+) -> Result<(u128, usize), DecodeError> {
+	decode_unsigned_from_slice(buf, MAX_LEN_U128, decode_u128)
+}
+
+/// Decodes a canonically-encoded `u8` from a slice, returning the value and
+/// encoded length.
+///
+/// Unlike [`decode_u8_from_slice`], an error is returned if the value is
+/// encoded with more bytes than would be produced by [`encode_u8`].
+///
+/// # Examples
+///
+/// ```
+/// let buf = [0x7F];
+/// assert_eq!(vu128::decode_u8_canonical(&buf), Ok((0x7F, 1)));
+///
+/// let buf = [0xF0, 0x7F];
+/// assert!(vu128::decode_u8_canonical(&buf).is_err());
+/// ```
+#[inline]
+pub fn decode_u8_canonical(buf: &[u8]) -> Result<(u8, usize), DecodeError> {
+	let (value, len) = decode_u8_from_slice(buf)?;
+	check_canonical(&buf[..len])?;
+	Ok((value, len))
+}
+
+/// Decodes a canonically-encoded `u16` from a slice, returning the value and
+/// encoded length.
+///
+/// Unlike [`decode_u16_from_slice`], an error is returned if the value is
+/// encoded with more bytes than would be produced by [`encode_u16`].
+///
+/// # Examples
+///
+/// ```
+/// let buf = [0x7F];
+/// assert_eq!(vu128::decode_u16_canonical(&buf), Ok((0x7F, 1)));
+///
+/// let buf = [0xF0, 0x7F];
+/// assert!(vu128::decode_u16_canonical(&buf).is_err());
+/// ```
+#[inline]
+pub fn decode_u16_canonical(buf: &[u8]) -> Result<(u16, usize), DecodeError> {
+	let (value, len) = decode_u16_from_slice(buf)?;
+	check_canonical(&buf[..len])?;
+	Ok((value, len))
+}
+
+/// Decodes a canonically-encoded `u32` from a slice, returning the value and
+/// encoded length.
+///
+/// Unlike [`decode_u32_from_slice`], an error is returned if the value is
+/// encoded with more bytes than would be produced by [`encode_u32`].
+///
+/// # Examples
+///
+/// ```
+/// # use vu128::{DecodeErrorKind, NonCanonicalReason};
+/// let buf = [0xB9, 0xC0];
+/// assert_eq!(vu128::decode_u32_canonical(&buf), Ok((12345, 2)));
+///
+/// let buf = [0xF1, 0x39, 0x30];
+/// let err = vu128::decode_u32_canonical(&buf).unwrap_err();
+/// let expect = NonCanonicalReason::BinaryLengthPrefix;
+/// assert_eq!(err.kind(), DecodeErrorKind::NonCanonical(expect));
+/// ```
+#[inline]
+pub fn decode_u32_canonical(buf: &[u8]) -> Result<(u32, usize), DecodeError> {
+	let (value, len) = decode_u32_from_slice(buf)?;
+	check_canonical(&buf[..len])?;
+	Ok((value, len))
+}
+
+/// Decodes a canonically-encoded `u64` from a slice, returning the value and
+/// encoded length.
+///
+/// Unlike [`decode_u64_from_slice`], an error is returned if the value is
+/// encoded with more bytes than would be produced by [`encode_u64`].
+///
+/// # Examples
+///
+/// ```
+/// # use vu128::{DecodeErrorKind, NonCanonicalReason};
+/// let buf = [0xB9, 0xC0];
+/// assert_eq!(vu128::decode_u64_canonical(&buf), Ok((12345, 2)));
+///
+/// let buf = [0xF1, 0x39, 0x30];
+/// let err = vu128::decode_u64_canonical(&buf).unwrap_err();
+/// let expect = NonCanonicalReason::BinaryLengthPrefix;
+/// assert_eq!(err.kind(), DecodeErrorKind::NonCanonical(expect));
+/// ```
+#[inline]
+pub fn decode_u64_canonical(buf: &[u8]) -> Result<(u64, usize), DecodeError> {
+	let (value, len) = decode_u64_from_slice(buf)?;
+	check_canonical(&buf[..len])?;
+	Ok((value, len))
+}
+
+/// Decodes a canonically-encoded `u128` from a slice, returning the value and
+/// encoded length.
+///
+/// Unlike [`decode_u128_from_slice`], an error is returned if the value is
+/// encoded with more bytes than would be produced by [`encode_u128`].
+///
+/// # Examples
+///
+/// ```
+/// # use vu128::{DecodeErrorKind, NonCanonicalReason};
+/// let buf = [0xB9, 0xC0];
+/// assert_eq!(vu128::decode_u128_canonical(&buf), Ok((12345, 2)));
+///
+/// let buf = [0xF1, 0x39, 0x30];
+/// let err = vu128::decode_u128_canonical(&buf).unwrap_err();
+/// let expect = NonCanonicalReason::BinaryLengthPrefix;
+/// assert_eq!(err.kind(), DecodeErrorKind::NonCanonical(expect));
+/// ```
+#[inline]
+pub fn decode_u128_canonical(
+	buf: &[u8],
+) -> Result<(u128, usize), DecodeError> {
+	let (value, len) = decode_u128_from_slice(buf)?;
+	check_canonical(&buf[..len])?;
+	Ok((value, len))
+}
+
+macro_rules! encode_iNN {
+	($(#[$docs:meta])* $name:ident ( $it:ident, $ut:ident, $buf:ident, $encode_fn:ident ) ) => {
+		$(#[$docs])*
+		#[inline]
+		#[must_use]
+		pub fn $name(buf: &mut $buf, value: $it) -> usize {
+			const ZIGZAG_SHIFT: u8 = ($ut::BITS as u8) - 1;
+			let zigzag = ((value >> ZIGZAG_SHIFT) as $ut) ^ ((value << 1) as $ut);
+			$encode_fn(buf, zigzag)
+		}
+	};
+}
+
+macro_rules! encode_iNN_uninit {
+	($(#[$docs:meta])* $name:ident ( $it:ident, $ut:ident, $max_len:ident, $encode_fn:ident ) ) => {
+		$(#[$docs])*
+		#[inline]
+		pub fn $name(
+			buf: &mut [MaybeUninit<u8>; $max_len],
+			value: $it,
+		) -> &[u8] {
+			const ZIGZAG_SHIFT: u8 = ($ut::BITS as u8) - 1;
+			let zigzag = ((value >> ZIGZAG_SHIFT) as $ut) ^ ((value << 1) as $ut);
+			$encode_fn(buf, zigzag)
+		}
+	};
+}
+
+macro_rules! encoded_len_iNN {
+	($(#[$docs:meta])* $name:ident ( $it:ident, $ut:ident, $len_fn:ident ) ) => {
+		$(#[$docs])*
+		#[must_use]
+		pub const fn $name(value: $it) -> usize {
+			const ZIGZAG_SHIFT: u8 = ($ut::BITS as u8) - 1;
+			let zigzag = ((value >> ZIGZAG_SHIFT) as $ut) ^ ((value << 1) as $ut);
+			$len_fn(zigzag)
+		}
+	};
+}
+
+macro_rules! decode_iNN {
+	($(#[$docs:meta])* $name:ident ( $it:ident, $buf:ident, $decode_fn:ident ) ) => {
+		$(#[$docs])*
+		#[inline]
+		#[must_use]
+		pub fn $name(buf: &$buf) -> ($it, usize) {
+			let (zz, len) = $decode_fn(buf);
+			let value = ((zz >> 1) as $it) ^ (-((zz & 1) as $it));
+			(value, len)
+		}
+	};
+}
+
+macro_rules! decode_iNN_result {
+	($(#[$docs:meta])* $name:ident ( $buf:ty => $it:ident, $decode_fn:ident ) ) => {
+		$(#[$docs])*
+		#[inline]
+		pub fn $name(buf: $buf) -> Result<($it, usize), DecodeError> {
+			let (zz, len) = $decode_fn(buf)?;
+			let value = ((zz >> 1) as $it) ^ (-((zz & 1) as $it));
+			Ok((value, len))
+		}
+	};
+}
+
+encode_iNN! {
+	/// Encodes an `i8` into a buffer, returning the encoded length.
+	///
+	/// The contents of the buffer beyond the returned length are unspecified.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 2];
+	/// let encoded_len = vu128::encode_i8(&mut buf, 100);
+	/// assert_eq!(&buf[..encoded_len], &[0x88, 0x03]);
+	/// ```
+	encode_i8(i8, u8, BufU8, encode_u8)
+}
+
+encode_iNN! {
+	/// Encodes an `i16` into a buffer, returning the encoded length.
+	///
+	/// The contents of the buffer beyond the returned length are unspecified.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 3];
+	/// let encoded_len = vu128::encode_i16(&mut buf, 100);
+	/// assert_eq!(&buf[..encoded_len], &[0x88, 0x03]);
+	/// ```
+	encode_i16(i16, u16, BufU16, encode_u16)
+}
+
+encode_iNN! {
+	/// Encodes an `i32` into a buffer, returning the encoded length.
+	///
+	/// The contents of the buffer beyond the returned length are unspecified.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 5];
+	/// let encoded_len = vu128::encode_i32(&mut buf, 123);
+	/// assert_eq!(&buf[..encoded_len], &[0xB6, 0x03]);
+	/// ```
+	encode_i32(i32, u32, BufU32, encode_u32)
+}
+
+encode_iNN! {
+	/// Encodes an `i64` into a buffer, returning the encoded length.
+	///
+	/// The contents of the buffer beyond the returned length are unspecified.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 9];
+	/// let encoded_len = vu128::encode_i64(&mut buf, 123);
+	/// assert_eq!(&buf[..encoded_len], &[0xB6, 0x03]);
+	/// ```
+	encode_i64(i64, u64, BufU64, encode_u64)
+}
+
+encode_iNN! {
+	/// Encodes an `i128` into a buffer, returning the encoded length.
+	///
+	/// The contents of the buffer beyond the returned length are unspecified.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 17];
+	/// let encoded_len = vu128::encode_i128(&mut buf, 123);
+	/// assert_eq!(&buf[..encoded_len], &[0xB6, 0x03]);
+	/// ```
+	encode_i128(i128, u128, BufU128, encode_u128)
+}
+
+encode_iNN_uninit! {
+	/// Encodes an `i8` into an uninitialized buffer, returning the encoded
+	/// bytes.
+	///
+	/// Only the returned prefix of the buffer is initialized.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use core::mem::MaybeUninit;
+	/// let mut buf = [MaybeUninit::uninit(); vu128::MAX_LEN_U8];
+	/// let encoded = vu128::encode_i8_uninit(&mut buf, -1);
+	/// assert_eq!(encoded, &[0x01]);
+	/// ```
+	encode_i8_uninit(i8, u8, MAX_LEN_U8, encode_u8_uninit)
+}
+
+encode_iNN_uninit! {
+	/// Encodes an `i16` into an uninitialized buffer, returning the encoded
+	/// bytes.
+	///
+	/// Only the returned prefix of the buffer is initialized.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use core::mem::MaybeUninit;
+	/// let mut buf = [MaybeUninit::uninit(); vu128::MAX_LEN_U16];
+	/// let encoded = vu128::encode_i16_uninit(&mut buf, -1);
+	/// assert_eq!(encoded, &[0x01]);
+	/// ```
+	encode_i16_uninit(i16, u16, MAX_LEN_U16, encode_u16_uninit)
+}
+
+encode_iNN_uninit! {
+	/// Encodes an `i32` into an uninitialized buffer, returning the encoded
+	/// bytes.
+	///
+	/// Only the returned prefix of the buffer is initialized.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use core::mem::MaybeUninit;
+	/// let mut buf = [MaybeUninit::uninit(); vu128::MAX_LEN_U32];
+	/// let encoded = vu128::encode_i32_uninit(&mut buf, -1);
+	/// assert_eq!(encoded, &[0x01]);
+	/// ```
+	encode_i32_uninit(i32, u32, MAX_LEN_U32, encode_u32_uninit)
+}
+
+encode_iNN_uninit! {
+	/// Encodes an `i64` into an uninitialized buffer, returning the encoded
+	/// bytes.
+	///
+	/// Only the returned prefix of the buffer is initialized.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use core::mem::MaybeUninit;
+	/// let mut buf = [MaybeUninit::uninit(); vu128::MAX_LEN_U64];
+	/// let encoded = vu128::encode_i64_uninit(&mut buf, -1);
+	/// assert_eq!(encoded, &[0x01]);
+	/// ```
+	encode_i64_uninit(i64, u64, MAX_LEN_U64, encode_u64_uninit)
+}
+
+encode_iNN_uninit! {
+	/// Encodes an `i128` into an uninitialized buffer, returning the encoded
+	/// bytes.
+	///
+	/// Only the returned prefix of the buffer is initialized.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use core::mem::MaybeUninit;
+	/// let mut buf = [MaybeUninit::uninit(); vu128::MAX_LEN_U128];
+	/// let encoded = vu128::encode_i128_uninit(&mut buf, -1);
+	/// assert_eq!(encoded, &[0x01]);
+	/// ```
+	encode_i128_uninit(i128, u128, MAX_LEN_U128, encode_u128_uninit)
+}
+
+encoded_len_iNN! {
+	/// Returns the length of an `i8` encoded by [`encode_i8`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 2];
+	/// let encoded_len = vu128::encode_i8(&mut buf, -100);
+	/// assert_eq!(vu128::encoded_len_i8(-100), encoded_len);
+	/// ```
+	encoded_len_i8(i8, u8, encoded_len_u8)
+}
+
+encoded_len_iNN! {
+	/// Returns the length of an `i16` encoded by [`encode_i16`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 3];
+	/// let encoded_len = vu128::encode_i16(&mut buf, -100);
+	/// assert_eq!(vu128::encoded_len_i16(-100), encoded_len);
+	/// ```
+	encoded_len_i16(i16, u16, encoded_len_u16)
+}
+
+encoded_len_iNN! {
+	/// Returns the length of an `i32` encoded by [`encode_i32`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 5];
+	/// let encoded_len = vu128::encode_i32(&mut buf, -12345);
+	/// assert_eq!(vu128::encoded_len_i32(-12345), encoded_len);
+	/// ```
+	encoded_len_i32(i32, u32, encoded_len_u32)
+}
+
+encoded_len_iNN! {
+	/// Returns the length of an `i64` encoded by [`encode_i64`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 9];
+	/// let encoded_len = vu128::encode_i64(&mut buf, -12345);
+	/// assert_eq!(vu128::encoded_len_i64(-12345), encoded_len);
+	/// ```
+	encoded_len_i64(i64, u64, encoded_len_u64)
+}
+
+encoded_len_iNN! {
+	/// Returns the length of an `i128` encoded by [`encode_i128`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 17];
+	/// let encoded_len = vu128::encode_i128(&mut buf, -12345);
+	/// assert_eq!(vu128::encoded_len_i128(-12345), encoded_len);
+	/// ```
+	encoded_len_i128(i128, u128, encoded_len_u128)
+}
+
+decode_iNN! {
+	/// Decodes an `i8` from a buffer, returning the value and encoded length.
+	///
+	/// If the encoded value does not fit in an `i8`, the returned value is
+	/// unspecified. Use [`decode_i8_checked`] to detect such values.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 2];
+	/// let encoded_len = vu128::encode_i8(&mut buf, -100);
+	/// assert_eq!(vu128::decode_i8(&buf), (-100, encoded_len));
+	/// ```
+	decode_i8(i8, BufU8, decode_u8)
+}
+
+decode_iNN! {
+	/// Decodes an `i16` from a buffer, returning the value and encoded length.
+	///
+	/// If the encoded value does not fit in an `i16`, the returned value is
+	/// unspecified. Use [`decode_i16_checked`] to detect such values.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 3];
+	/// let encoded_len = vu128::encode_i16(&mut buf, -100);
+	/// assert_eq!(vu128::decode_i16(&buf), (-100, encoded_len));
+	/// ```
+	decode_i16(i16, BufU16, decode_u16)
+}
+
+decode_iNN! {
+	/// Decodes an `i32` from a buffer, returning the value and encoded length.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 5];
+	/// let encoded_len = vu128::encode_i32(&mut buf, 123);
+	/// assert_eq!(vu128::decode_i32(&buf), (123, encoded_len));
+	/// ```
+	decode_i32(i32, BufU32, decode_u32)
+}
+
+decode_iNN! {
+	/// Decodes an `i64` from a buffer, returning the value and encoded length.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 9];
+	/// let encoded_len = vu128::encode_i64(&mut buf, 123);
+	/// assert_eq!(vu128::decode_i64(&buf), (123, encoded_len));
+	/// ```
+	decode_i64(i64, BufU64, decode_u64)
+}
+
+decode_iNN! {
+	/// Decodes an `i128` from a buffer, returning the value and encoded length.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 17];
+	/// let encoded_len = vu128::encode_i128(&mut buf, 123);
+	/// assert_eq!(vu128::decode_i128(&buf), (123, encoded_len));
+	/// ```
+	decode_i128(i128, BufU128, decode_u128)
+}
+
+decode_iNN_result! {
+	/// Decodes an `i8` from a buffer, returning the value and encoded length.
+	///
+	/// Unlike [`decode_i8`], an error is returned if the encoded value does
+	/// not fit in an `i8`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 2];
+	/// let encoded_len = vu128::encode_i8(&mut buf, -100);
+	/// assert_eq!(vu128::decode_i8_checked(&buf), Ok((-100, encoded_len)));
+	/// ```
+	decode_i8_checked(&BufU8 => i8, decode_u8_checked)
+}
+
+decode_iNN_result! {
+	/// Decodes an `i16` from a buffer, returning the value and encoded length.
+	///
+	/// Unlike [`decode_i16`], an error is returned if the encoded value does
+	/// not fit in an `i16`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 3];
+	/// let encoded_len = vu128::encode_i16(&mut buf, -100);
+	/// assert_eq!(vu128::decode_i16_checked(&buf), Ok((-100, encoded_len)));
+	/// ```
+	decode_i16_checked(&BufU16 => i16, decode_u16_checked)
+}
+
+decode_iNN_result! {
+	/// Decodes an `i32` from a buffer, returning the value and encoded length.
+	///
+	/// Unlike [`decode_i32`], an error is returned if the prefix byte
+	/// indicates an encoded length greater than the buffer.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 5];
+	/// let encoded_len = vu128::encode_i32(&mut buf, 123);
+	/// assert_eq!(vu128::decode_i32_checked(&buf), Ok((123, encoded_len)));
+	///
+	/// let buf = [0xF4, 0x00, 0x00, 0x00, 0x00];
+	/// assert!(vu128::decode_i32_checked(&buf).is_err());
+	/// ```
+	decode_i32_checked(&BufU32 => i32, decode_u32_checked)
+}
+
+decode_iNN_result! {
+	/// Decodes an `i64` from a buffer, returning the value and encoded length.
+	///
+	/// Unlike [`decode_i64`], an error is returned if the prefix byte
+	/// indicates an encoded length greater than the buffer.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 9];
+	/// let encoded_len = vu128::encode_i64(&mut buf, 123);
+	/// assert_eq!(vu128::decode_i64_checked(&buf), Ok((123, encoded_len)));
+	///
+	/// let buf = [0xFF, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+	/// assert!(vu128::decode_i64_checked(&buf).is_err());
+	/// ```
+	decode_i64_checked(&BufU64 => i64, decode_u64_checked)
+}
+
+decode_iNN_result! {
+	/// Decodes an `i8` from a slice, returning the value and encoded length.
+	///
+	/// Unlike [`decode_i8`], the input may be of any length. An error is
+	/// returned if the slice is shorter than the encoded length indicated by
+	/// its first byte, or if the encoded value does not fit in an `i8`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let buf = [0x88, 0x03];
+	/// assert_eq!(vu128::decode_i8_from_slice(&buf), Ok((100, 2)));
+	/// assert!(vu128::decode_i8_from_slice(&buf[..1]).is_err());
+	/// ```
+	decode_i8_from_slice(&[u8] => i8, decode_u8_from_slice)
+}
+
+decode_iNN_result! {
+	/// Decodes an `i16` from a slice, returning the value and encoded length.
+	///
+	/// Unlike [`decode_i16`], the input may be of any length. An error is
+	/// returned if the slice is shorter than the encoded length indicated by
+	/// its first byte, or if the encoded value does not fit in an `i16`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let buf = [0x88, 0x03];
+	/// assert_eq!(vu128::decode_i16_from_slice(&buf), Ok((100, 2)));
+	/// assert!(vu128::decode_i16_from_slice(&buf[..1]).is_err());
+	/// ```
+	decode_i16_from_slice(&[u8] => i16, decode_u16_from_slice)
+}
+
+decode_iNN_result! {
+	/// Decodes an `i32` from a slice, returning the value and encoded length.
+	///
+	/// Unlike [`decode_i32`], the input may be of any length. An error is
+	/// returned if the slice is shorter than the encoded length indicated by
+	/// its first byte, or if the encoded value does not fit in an `i32`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let buf = [0xB6, 0x03];
+	/// assert_eq!(vu128::decode_i32_from_slice(&buf), Ok((123, 2)));
+	/// assert!(vu128::decode_i32_from_slice(&buf[..1]).is_err());
+	/// ```
+	decode_i32_from_slice(&[u8] => i32, decode_u32_from_slice)
+}
+
+decode_iNN_result! {
+	/// Decodes an `i64` from a slice, returning the value and encoded length.
+	///
+	/// Unlike [`decode_i64`], the input may be of any length. An error is
+	/// returned if the slice is shorter than the encoded length indicated by
+	/// its first byte, or if the encoded value does not fit in an `i64`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let buf = [0xB6, 0x03];
+	/// assert_eq!(vu128::decode_i64_from_slice(&buf), Ok((123, 2)));
+	/// assert!(vu128::decode_i64_from_slice(&buf[..1]).is_err());
+	/// ```
+	decode_i64_from_slice(&[u8] => i64, decode_u64_from_slice)
+}
+
+decode_iNN_result! {
+	/// Decodes an `i128` from a slice, returning the value and encoded length.
+	///
+	/// Unlike [`decode_i128`], the input may be of any length. An error is
+	/// returned if the slice is shorter than the encoded length indicated by
+	/// its first byte.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let buf = [0xB6, 0x03];
+	/// assert_eq!(vu128::decode_i128_from_slice(&buf), Ok((123, 2)));
+	/// assert!(vu128::decode_i128_from_slice(&buf[..1]).is_err());
+	/// ```
+	decode_i128_from_slice(&[u8] => i128, decode_u128_from_slice)
+}
+
+decode_iNN_result! {
+	/// Decodes a canonically-encoded `i8` from a slice, returning the value
+	/// and encoded length.
+	///
+	/// Unlike [`decode_i8_from_slice`], an error is returned if the value is
+	/// encoded with more bytes than would be produced by [`encode_i8`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let buf = [0x88, 0x03];
+	/// assert_eq!(vu128::decode_i8_canonical(&buf), Ok((100, 2)));
+	///
+	/// let buf = [0xF0, 0xC8];
+	/// assert!(vu128::decode_i8_canonical(&buf).is_err());
+	/// ```
+	decode_i8_canonical(&[u8] => i8, decode_u8_canonical)
+}
+
+decode_iNN_result! {
+	/// Decodes a canonically-encoded `i16` from a slice, returning the value
+	/// and encoded length.
+	///
+	/// Unlike [`decode_i16_from_slice`], an error is returned if the value is
+	/// encoded with more bytes than would be produced by [`encode_i16`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let buf = [0x88, 0x03];
+	/// assert_eq!(vu128::decode_i16_canonical(&buf), Ok((100, 2)));
+	///
+	/// let buf = [0xF0, 0xC8];
+	/// assert!(vu128::decode_i16_canonical(&buf).is_err());
+	/// ```
+	decode_i16_canonical(&[u8] => i16, decode_u16_canonical)
+}
+
+decode_iNN_result! {
+	/// Decodes a canonically-encoded `i32` from a slice, returning the value
+	/// and encoded length.
+	///
+	/// Unlike [`decode_i32_from_slice`], an error is returned if the value is
+	/// encoded with more bytes than would be produced by [`encode_i32`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let buf = [0xB6, 0x03];
+	/// assert_eq!(vu128::decode_i32_canonical(&buf), Ok((123, 2)));
+	///
+	/// let buf = [0xF1, 0xF6, 0x00];
+	/// assert!(vu128::decode_i32_canonical(&buf).is_err());
+	/// ```
+	decode_i32_canonical(&[u8] => i32, decode_u32_canonical)
+}
+
+decode_iNN_result! {
+	/// Decodes a canonically-encoded `i64` from a slice, returning the value
+	/// and encoded length.
+	///
+	/// Unlike [`decode_i64_from_slice`], an error is returned if the value is
+	/// encoded with more bytes than would be produced by [`encode_i64`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let buf = [0xB6, 0x03];
+	/// assert_eq!(vu128::decode_i64_canonical(&buf), Ok((123, 2)));
+	///
+	/// let buf = [0xF1, 0xF6, 0x00];
+	/// assert!(vu128::decode_i64_canonical(&buf).is_err());
+	/// ```
+	decode_i64_canonical(&[u8] => i64, decode_u64_canonical)
+}
+
+decode_iNN_result! {
+	/// Decodes a canonically-encoded `i128` from a slice, returning the value
+	/// and encoded length.
+	///
+	/// Unlike [`decode_i128_from_slice`], an error is returned if the value is
+	/// encoded with more bytes than would be produced by [`encode_i128`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let buf = [0xB6, 0x03];
+	/// assert_eq!(vu128::decode_i128_canonical(&buf), Ok((123, 2)));
+	///
+	/// let buf = [0xF1, 0xF6, 0x00];
+	/// assert!(vu128::decode_i128_canonical(&buf).is_err());
+	/// ```
+	decode_i128_canonical(&[u8] => i128, decode_u128_canonical)
+}
+
+/// Encodes an `f32` into a buffer, returning the encoded length.
+///
+/// The contents of the buffer beyond the returned length are unspecified.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 5];
+/// let encoded_len = vu128::encode_f32(&mut buf, 2.5);
+/// assert_eq!(&buf[..encoded_len], &[0x80, 0x81]);
+/// ```
+#[inline]
+#[must_use]
+pub fn encode_f32(buf: &mut BufU32, value: f32) -> usize {
+	encode_u32(buf, value.to_bits().swap_bytes())
+}
+
+/// Encodes an `f64` into a buffer, returning the encoded length.
+///
+/// The contents of the buffer beyond the returned length are unspecified.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 9];
+/// let encoded_len = vu128::encode_f64(&mut buf, 2.5);
+/// assert_eq!(&buf[..encoded_len], &[0x80, 0x11]);
+/// ```
+#[inline]
+#[must_use]
+pub fn encode_f64(buf: &mut BufU64, value: f64) -> usize {
+	encode_u64(buf, value.to_bits().swap_bytes())
+}
+
+/// Encodes an `f32` into an uninitialized buffer, returning the encoded
+/// bytes.
+///
+/// Only the returned prefix of the buffer is initialized.
+///
+/// # Examples
+///
+/// ```
+/// # use core::mem::MaybeUninit;
+/// let mut buf = [MaybeUninit::uninit(); vu128::MAX_LEN_U32];
+/// let encoded = vu128::encode_f32_uninit(&mut buf, 2.5);
+/// assert_eq!(encoded, &[0x80, 0x81]);
+/// ```
+#[inline]
+pub fn encode_f32_uninit(
+	buf: &mut [MaybeUninit<u8>; MAX_LEN_U32],
+	value: f32,
+) -> &[u8] {
+	encode_u32_uninit(buf, value.to_bits().swap_bytes())
+}
+
+/// Encodes an `f64` into an uninitialized buffer, returning the encoded
+/// bytes.
+///
+/// Only the returned prefix of the buffer is initialized.
+///
+/// # Examples
+///
+/// ```
+/// # use core::mem::MaybeUninit;
+/// let mut buf = [MaybeUninit::uninit(); vu128::MAX_LEN_U64];
+/// let encoded = vu128::encode_f64_uninit(&mut buf, 2.5);
+/// assert_eq!(encoded, &[0x80, 0x11]);
+/// ```
+#[inline]
+pub fn encode_f64_uninit(
+	buf: &mut [MaybeUninit<u8>; MAX_LEN_U64],
+	value: f64,
+) -> &[u8] {
+	encode_u64_uninit(buf, value.to_bits().swap_bytes())
+}
+
+/// Returns the length of an `f32` encoded by [`encode_f32`].
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 5];
+/// let encoded_len = vu128::encode_f32(&mut buf, 2.5);
+/// assert_eq!(vu128::encoded_len_f32(2.5), encoded_len);
+/// ```
+#[must_use]
+pub fn encoded_len_f32(value: f32) -> usize {
+	encoded_len_u32(value.to_bits().swap_bytes())
+}
+
+/// Returns the length of an `f64` encoded by [`encode_f64`].
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 9];
+/// let encoded_len = vu128::encode_f64(&mut buf, 2.5);
+/// assert_eq!(vu128::encoded_len_f64(2.5), encoded_len);
+/// ```
+#[must_use]
+pub fn encoded_len_f64(value: f64) -> usize {
+	encoded_len_u64(value.to_bits().swap_bytes())
+}
+
+/// Decodes an `f32` from a buffer, returning the value and encoded length.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 5];
+/// let encoded_len = vu128::encode_f32(&mut buf, 2.5);
+/// assert_eq!(vu128::decode_f32(&buf), (2.5, encoded_len));
+/// ```
+#[inline]
+#[must_use]
+pub fn decode_f32(buf: &BufU32) -> (f32, usize) {
+	let (swapped, len) = decode_u32(buf);
+	(f32::from_bits(swapped.swap_bytes()), len)
+}
+
+/// Decodes an `f64` from a buffer, returning the value and encoded length.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 9];
+/// let encoded_len = vu128::encode_f64(&mut buf, 2.5);
+/// assert_eq!(vu128::decode_f64(&buf), (2.5, encoded_len));
+/// ```
+#[inline]
+#[must_use]
+pub fn decode_f64(buf: &BufU64) -> (f64, usize) {
+	let (swapped, len) = decode_u64(buf);
+	(f64::from_bits(swapped.swap_bytes()), len)
+}
+
+/// Decodes an `f32` from a buffer, returning the value and encoded length.
+///
+/// Unlike [`decode_f32`], an error is returned if the prefix byte indicates an
+/// encoded length greater than the buffer.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 5];
+/// let encoded_len = vu128::encode_f32(&mut buf, 2.5);
+/// assert_eq!(vu128::decode_f32_checked(&buf), Ok((2.5, encoded_len)));
+///
+/// let buf = [0xF4, 0x00, 0x00, 0x00, 0x00];
+/// assert!(vu128::decode_f32_checked(&buf).is_err());
+/// ```
+#[inline]
+pub fn decode_f32_checked(buf: &BufU32) -> Result<(f32, usize), DecodeError> {
+	let (swapped, len) = decode_u32_checked(buf)?;
+	Ok((f32::from_bits(swapped.swap_bytes()), len))
+}
+
+/// Decodes an `f64` from a buffer, returning the value and encoded length.
+///
+/// Unlike [`decode_f64`], an error is returned if the prefix byte indicates an
+/// encoded length greater than the buffer.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 9];
+/// let encoded_len = vu128::encode_f64(&mut buf, 2.5);
+/// assert_eq!(vu128::decode_f64_checked(&buf), Ok((2.5, encoded_len)));
+///
+/// let buf = [0xFF, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+/// assert!(vu128::decode_f64_checked(&buf).is_err());
+/// ```
+#[inline]
+pub fn decode_f64_checked(buf: &BufU64) -> Result<(f64, usize), DecodeError> {
+	let (swapped, len) = decode_u64_checked(buf)?;
+	Ok((f64::from_bits(swapped.swap_bytes()), len))
+}
+
+/// Decodes an `f32` from a slice, returning the value and encoded length.
+///
+/// Unlike [`decode_f32`], the input may be of any length. An error is
+/// returned if the slice is shorter than the encoded length indicated by its
+/// first byte, or if the encoded value does not fit in an `f32`.
+///
+/// # Examples
+///
+/// ```
+/// let buf = [0x80, 0x81];
+/// assert_eq!(vu128::decode_f32_from_slice(&buf), Ok((2.5, 2)));
+/// assert!(vu128::decode_f32_from_slice(&buf[..1]).is_err());
+/// ```
+#[inline]
+pub fn decode_f32_from_slice(buf: &[u8]) -> Result<(f32, usize), DecodeError> {
+	let (swapped, len) = decode_u32_from_slice(buf)?;
+	Ok((f32::from_bits(swapped.swap_bytes()), len))
+}
+
+/// Decodes an `f64` from a slice, returning the value and encoded length.
+///
+/// Unlike [`decode_f64`], the input may be of any length. An error is
+/// returned if the slice is shorter than the encoded length indicated by its
+/// first byte, or if the encoded value does not fit in an `f64`.
+///
+/// # Examples
+///
+/// ```
+/// let buf = [0x80, 0x11];
+/// assert_eq!(vu128::decode_f64_from_slice(&buf), Ok((2.5, 2)));
+/// assert!(vu128::decode_f64_from_slice(&buf[..1]).is_err());
+/// ```
+#[inline]
+pub fn decode_f64_from_slice(buf: &[u8]) -> Result<(f64, usize), DecodeError> {
+	let (swapped, len) = decode_u64_from_slice(buf)?;
+	Ok((f64::from_bits(swapped.swap_bytes()), len))
+}
+
+/// Decodes a canonically-encoded `f32` from a slice, returning the value and
+/// encoded length.
+///
+/// Unlike [`decode_f32_from_slice`], an error is returned if the value is
+/// encoded with more bytes than would be produced by [`encode_f32`].
+///
+/// # Examples
+///
+/// ```
+/// let buf = [0x80, 0x81];
+/// assert_eq!(vu128::decode_f32_canonical(&buf), Ok((2.5, 2)));
+///
+/// let buf = [0xC0, 0x02, 0x01];
+/// assert!(vu128::decode_f32_canonical(&buf).is_err());
+/// ```
+#[inline]
+pub fn decode_f32_canonical(buf: &[u8]) -> Result<(f32, usize), DecodeError> {
+	let (swapped, len) = decode_u32_canonical(buf)?;
+	Ok((f32::from_bits(swapped.swap_bytes()), len))
+}
+
+/// Decodes a canonically-encoded `f64` from a slice, returning the value and
+/// encoded length.
+///
+/// Unlike [`decode_f64_from_slice`], an error is returned if the value is
+/// encoded with more bytes than would be produced by [`encode_f64`].
+///
+/// # Examples
+///
+/// ```
+/// let buf = [0x80, 0x11];
+/// assert_eq!(vu128::decode_f64_canonical(&buf), Ok((2.5, 2)));
+///
+/// let buf = [0xC0, 0x22, 0x00];
+/// assert!(vu128::decode_f64_canonical(&buf).is_err());
+/// ```
+#[inline]
+pub fn decode_f64_canonical(buf: &[u8]) -> Result<(f64, usize), DecodeError> {
+	let (swapped, len) = decode_u64_canonical(buf)?;
+	Ok((f64::from_bits(swapped.swap_bytes()), len))
+}
+
+/// Encodes a `usize` into a buffer, returning the encoded length.
+///
+/// Values are encoded as `u64`, so the output does not depend on the width of
+/// `usize` on the encoding platform.
+///
+/// The contents of the buffer beyond the returned length are unspecified.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 9];
+/// let encoded_len = vu128::encode_usize(&mut buf, 12345);
+/// assert_eq!(&buf[..encoded_len], &[0xB9, 0xC0]);
+/// ```
+#[inline]
+#[must_use]
+pub fn encode_usize(buf: &mut BufU64, value: usize) -> usize {
+	encode_u64(buf, value as u64)
+}
+
+/// Encodes a `usize` into an uninitialized buffer, returning the encoded
+/// bytes.
+///
+/// Only the returned prefix of the buffer is initialized.
+///
+/// # Examples
+///
+/// ```
+/// # use core::mem::MaybeUninit;
+/// let mut buf = [MaybeUninit::uninit(); vu128::MAX_LEN_U64];
+/// let encoded = vu128::encode_usize_uninit(&mut buf, 123);
+/// assert_eq!(encoded, &[0x7B]);
+/// ```
+#[inline]
+pub fn encode_usize_uninit(
+	buf: &mut [MaybeUninit<u8>; MAX_LEN_U64],
+	value: usize,
+) -> &[u8] {
+	encode_u64_uninit(buf, value as u64)
+}
+
+/// Returns the length of a `usize` encoded by [`encode_usize`].
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 9];
+/// let encoded_len = vu128::encode_usize(&mut buf, 12345);
+/// assert_eq!(vu128::encoded_len_usize(12345), encoded_len);
+/// ```
+#[must_use]
+pub const fn encoded_len_usize(value: usize) -> usize {
+	encoded_len_u64(value as u64)
+}
+
+/// Decodes a `usize` from a buffer, returning the value and encoded length.
+///
+/// If the encoded value does not fit in a `usize`, the returned value is
+/// unspecified. Use [`decode_usize_checked`] to detect such values.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 9];
+/// let encoded_len = vu128::encode_usize(&mut buf, 12345);
+/// assert_eq!(vu128::decode_usize(&buf), (12345, encoded_len));
+/// ```
+#[inline]
+#[must_use]
+pub fn decode_usize(buf: &BufU64) -> (usize, usize) {
+	let (value, len) = decode_u64(buf);
+	(value as usize, len)
+}
+
+/// Decodes a `usize` from a buffer, returning the value and encoded length.
+///
+/// Unlike [`decode_usize`], an error is returned if the encoded value does
+/// not fit in a `usize`, such as a value greater than `u32::MAX` on a 32-bit
+/// platform. The error kind for such values is [`DecodeErrorKind::OutOfRange`].
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 9];
+/// let encoded_len = vu128::encode_usize(&mut buf, 12345);
+/// assert_eq!(vu128::decode_usize_checked(&buf), Ok((12345, encoded_len)));
+/// ```
+#[inline]
+pub fn decode_usize_checked(
+	buf: &BufU64,
+) -> Result<(usize, usize), DecodeError> {
+	let (value, len) = decode_u64_checked(buf)?;
+	narrow_decoded(value, len)
+}
+
+/// Decodes a `usize` from a slice, returning the value and encoded length.
+///
+/// Unlike [`decode_usize`], the input may be of any length. An error is
+/// returned if the slice is shorter than the encoded length indicated by its
+/// first byte, or if the encoded value does not fit in a `usize`. The error
+/// kind for values that do not fit is [`DecodeErrorKind::OutOfRange`].
+///
+/// # Examples
+///
+/// ```
+/// let buf = [0xB9, 0xC0];
+/// assert_eq!(vu128::decode_usize_from_slice(&buf), Ok((12345, 2)));
+/// assert!(vu128::decode_usize_from_slice(&buf[..1]).is_err());
+/// ```
+#[inline]
+pub fn decode_usize_from_slice(
+	buf: &[u8],
+) -> Result<(usize, usize), DecodeError> {
+	let (value, len) = decode_u64_from_slice(buf)?;
+	narrow_decoded(value, len)
+}
+
+/// Decodes a canonically-encoded `usize` from a slice, returning the value
+/// and encoded length.
+///
+/// Unlike [`decode_usize_from_slice`], an error is returned if the value is
+/// encoded with more bytes than would be produced by [`encode_usize`]. As with
+/// [`decode_usize_from_slice`], values that do not fit in a `usize` are
+/// rejected with [`DecodeErrorKind::OutOfRange`].
+///
+/// # Examples
+///
+/// ```
+/// let buf = [0x7F];
+/// assert_eq!(vu128::decode_usize_canonical(&buf), Ok((0x7F, 1)));
+///
+/// let buf = [0xF0, 0x7F];
+/// assert!(vu128::decode_usize_canonical(&buf).is_err());
+/// ```
+#[inline]
+pub fn decode_usize_canonical(
+	buf: &[u8],
+) -> Result<(usize, usize), DecodeError> {
+	let (value, len) = decode_u64_canonical(buf)?;
+	narrow_decoded(value, len)
+}
+
+encode_iNN! {
+	/// Encodes an `isize` into a buffer, returning the encoded length.
+	///
+	/// Values are encoded as `i64`, so the output does not depend on the
+	/// width of `isize` on the encoding platform.
+	///
+	/// The contents of the buffer beyond the returned length are unspecified.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 9];
+	/// let encoded_len = vu128::encode_isize(&mut buf, -1);
+	/// assert_eq!(&buf[..encoded_len], &[0x01]);
+	/// ```
+	encode_isize(isize, usize, BufU64, encode_usize)
+}
+
+encode_iNN_uninit! {
+	/// Encodes an `isize` into an uninitialized buffer, returning the encoded
+	/// bytes.
+	///
+	/// Only the returned prefix of the buffer is initialized.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use core::mem::MaybeUninit;
+	/// let mut buf = [MaybeUninit::uninit(); vu128::MAX_LEN_U64];
+	/// let encoded = vu128::encode_isize_uninit(&mut buf, -1);
+	/// assert_eq!(encoded, &[0x01]);
+	/// ```
+	encode_isize_uninit(isize, usize, MAX_LEN_U64, encode_usize_uninit)
+}
+
+encoded_len_iNN! {
+	/// Returns the length of an `isize` encoded by [`encode_isize`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 9];
+	/// let encoded_len = vu128::encode_isize(&mut buf, -12345);
+	/// assert_eq!(vu128::encoded_len_isize(-12345), encoded_len);
+	/// ```
+	encoded_len_isize(isize, usize, encoded_len_usize)
+}
+
+decode_iNN! {
+	/// Decodes an `isize` from a buffer, returning the value and encoded
+	/// length.
+	///
+	/// If the encoded value does not fit in an `isize`, the returned value is
+	/// unspecified. Use [`decode_isize_checked`] to detect such values.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 9];
+	/// let encoded_len = vu128::encode_isize(&mut buf, -12345);
+	/// assert_eq!(vu128::decode_isize(&buf), (-12345, encoded_len));
+	/// ```
+	decode_isize(isize, BufU64, decode_usize)
+}
+
+decode_iNN_result! {
+	/// Decodes an `isize` from a buffer, returning the value and encoded
+	/// length.
+	///
+	/// Unlike [`decode_isize`], an error is returned if the encoded value
+	/// does not fit in an `isize`, such as a value outside the range of `i32`
+	/// on a 32-bit platform. The error kind for such values is
+	/// [`DecodeErrorKind::OutOfRange`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 9];
+	/// let encoded_len = vu128::encode_isize(&mut buf, -12345);
+	/// let decoded = vu128::decode_isize_checked(&buf);
+	/// assert_eq!(decoded, Ok((-12345, encoded_len)));
+	/// ```
+	decode_isize_checked(&BufU64 => isize, decode_usize_checked)
+}
+
+decode_iNN_result! {
+	/// Decodes an `isize` from a slice, returning the value and encoded
+	/// length.
+	///
+	/// Unlike [`decode_isize`], the input may be of any length. An error is
+	/// returned if the slice is shorter than the encoded length indicated by
+	/// its first byte, or if the encoded value does not fit in an `isize`.
+	/// The error kind for values that do not fit is
+	/// [`DecodeErrorKind::OutOfRange`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let buf = [0x01];
+	/// assert_eq!(vu128::decode_isize_from_slice(&buf), Ok((-1, 1)));
+	/// assert!(vu128::decode_isize_from_slice(&[0x80]).is_err());
+	/// ```
+	decode_isize_from_slice(&[u8] => isize, decode_usize_from_slice)
+}
+
+decode_iNN_result! {
+	/// Decodes a canonically-encoded `isize` from a slice, returning the
+	/// value and encoded length.
+	///
+	/// Unlike [`decode_isize_from_slice`], an error is returned if the value
+	/// is encoded with more bytes than would be produced by [`encode_isize`].
+	/// As with [`decode_isize_from_slice`], values that do not fit in an
+	/// `isize` are rejected with [`DecodeErrorKind::OutOfRange`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let buf = [0x01];
+	/// assert_eq!(vu128::decode_isize_canonical(&buf), Ok((-1, 1)));
+	///
+	/// let buf = [0xF0, 0x01];
+	/// assert!(vu128::decode_isize_canonical(&buf).is_err());
+	/// ```
+	decode_isize_canonical(&[u8] => isize, decode_usize_canonical)
+}
+
+/// Encodes a `NonZeroU32` into a buffer, returning the encoded length.
+///
+/// The value is decremented before encoding, so that `1` is encoded as `0`
+/// and values up to 128 fit in a single byte.
+///
+/// The contents of the buffer beyond the returned length are unspecified.
+///
+/// # Examples
+///
+/// ```
+/// # use core::num::NonZeroU32;
+/// let mut buf = [0u8; 5];
+/// let value = NonZeroU32::new(128).unwrap();
+/// let encoded_len = vu128::encode_nonzero_u32(&mut buf, value);
+/// assert_eq!(&buf[..encoded_len], &[0x7F]);
+/// ```
+#[inline]
+#[must_use]
+pub fn encode_nonzero_u32(buf: &mut BufU32, value: NonZeroU32) -> usize {
+	encode_u32(buf, value.get() - 1)
+}
+
+/// Returns the length of a `NonZeroU32` encoded by [`encode_nonzero_u32`].
+///
+/// # Examples
+///
+/// ```
+/// # use core::num::NonZeroU32;
+/// let value = NonZeroU32::new(129).unwrap();
+/// assert_eq!(vu128::encoded_len_nonzero_u32(value), 2);
+/// ```
+#[must_use]
+pub const fn encoded_len_nonzero_u32(value: NonZeroU32) -> usize {
+	encoded_len_u32(value.get() - 1)
+}
+
+/// Decodes a `NonZeroU32` from a buffer, returning the value and encoded
+/// length.
+///
+/// An error is returned if the encoded value is `u32::MAX`, which has no
+/// corresponding `NonZeroU32`.
+///
+/// # Examples
+///
+/// ```
+/// # use core::num::NonZeroU32;
+/// let mut buf = [0u8; 5];
+/// let value = NonZeroU32::new(128).unwrap();
+/// let encoded_len = vu128::encode_nonzero_u32(&mut buf, value);
+/// assert_eq!(vu128::decode_nonzero_u32(&buf), Ok((value, encoded_len)));
+/// ```
+#[inline]
+pub fn decode_nonzero_u32(
+	buf: &BufU32,
+) -> Result<(NonZeroU32, usize), DecodeError> {
+	let (value, len) = decode_u32_checked(buf)?;
+	nonzero_decoded(NonZeroU32::new(value.wrapping_add(1)), len)
+}
+
+/// Decodes a `NonZeroU32` from a slice, returning the value and encoded
+/// length.
+///
+/// Unlike [`decode_nonzero_u32`], the input may be of any length. An error is
+/// returned if the slice is shorter than the encoded length indicated by its
+/// first byte, or if the encoded value has no corresponding `NonZeroU32`.
+///
+/// # Examples
+///
+/// ```
+/// # use core::num::NonZeroU32;
+/// let value = NonZeroU32::new(128).unwrap();
+/// let decoded = vu128::decode_nonzero_u32_from_slice(&[0x7F]);
+/// assert_eq!(decoded, Ok((value, 1)));
+/// ```
+#[inline]
+pub fn decode_nonzero_u32_from_slice(
+	buf: &[u8],
+) -> Result<(NonZeroU32, usize), DecodeError> {
+	let (value, len) = decode_u32_from_slice(buf)?;
+	nonzero_decoded(NonZeroU32::new(value.wrapping_add(1)), len)
+}
+
+/// Encodes a `NonZeroU64` into a buffer, returning the encoded length.
+///
+/// The value is decremented before encoding, so that `1` is encoded as `0`
+/// and values up to 128 fit in a single byte.
+///
+/// The contents of the buffer beyond the returned length are unspecified.
+///
+/// # Examples
+///
+/// ```
+/// # use core::num::NonZeroU64;
+/// let mut buf = [0u8; 9];
+/// let value = NonZeroU64::new(128).unwrap();
+/// let encoded_len = vu128::encode_nonzero_u64(&mut buf, value);
+/// assert_eq!(&buf[..encoded_len], &[0x7F]);
+/// ```
+#[inline]
+#[must_use]
+pub fn encode_nonzero_u64(buf: &mut BufU64, value: NonZeroU64) -> usize {
+	encode_u64(buf, value.get() - 1)
+}
+
+/// Returns the length of a `NonZeroU64` encoded by [`encode_nonzero_u64`].
+///
+/// # Examples
+///
+/// ```
+/// # use core::num::NonZeroU64;
+/// let value = NonZeroU64::new(129).unwrap();
+/// assert_eq!(vu128::encoded_len_nonzero_u64(value), 2);
+/// ```
+#[must_use]
+pub const fn encoded_len_nonzero_u64(value: NonZeroU64) -> usize {
+	encoded_len_u64(value.get() - 1)
+}
+
+/// Decodes a `NonZeroU64` from a buffer, returning the value and encoded
+/// length.
+///
+/// An error is returned if the encoded value is `u64::MAX`, which has no
+/// corresponding `NonZeroU64`.
+///
+/// # Examples
+///
+/// ```
+/// # use core::num::NonZeroU64;
+/// let mut buf = [0u8; 9];
+/// let value = NonZeroU64::new(128).unwrap();
+/// let encoded_len = vu128::encode_nonzero_u64(&mut buf, value);
+/// assert_eq!(vu128::decode_nonzero_u64(&buf), Ok((value, encoded_len)));
+/// ```
+#[inline]
+pub fn decode_nonzero_u64(
+	buf: &BufU64,
+) -> Result<(NonZeroU64, usize), DecodeError> {
+	let (value, len) = decode_u64_checked(buf)?;
+	nonzero_decoded(NonZeroU64::new(value.wrapping_add(1)), len)
+}
+
+/// Decodes a `NonZeroU64` from a slice, returning the value and encoded
+/// length.
+///
+/// Unlike [`decode_nonzero_u64`], the input may be of any length. An error is
+/// returned if the slice is shorter than the encoded length indicated by its
+/// first byte, or if the encoded value has no corresponding `NonZeroU64`.
+///
+/// # Examples
+///
+/// ```
+/// # use core::num::NonZeroU64;
+/// let value = NonZeroU64::new(128).unwrap();
+/// let decoded = vu128::decode_nonzero_u64_from_slice(&[0x7F]);
+/// assert_eq!(decoded, Ok((value, 1)));
+/// ```
+#[inline]
+pub fn decode_nonzero_u64_from_slice(
+	buf: &[u8],
+) -> Result<(NonZeroU64, usize), DecodeError> {
+	let (value, len) = decode_u64_from_slice(buf)?;
+	nonzero_decoded(NonZeroU64::new(value.wrapping_add(1)), len)
+}
+
+/// Encodes a `NonZeroU128` into a buffer, returning the encoded length.
+///
+/// The value is decremented before encoding, so that `1` is encoded as `0`
+/// and values up to 128 fit in a single byte.
+///
+/// The contents of the buffer beyond the returned length are unspecified.
+///
+/// # Examples
+///
+/// ```
+/// # use core::num::NonZeroU128;
+/// let mut buf = [0u8; 17];
+/// let value = NonZeroU128::new(128).unwrap();
+/// let encoded_len = vu128::encode_nonzero_u128(&mut buf, value);
+/// assert_eq!(&buf[..encoded_len], &[0x7F]);
+/// ```
+#[inline]
+#[must_use]
+pub fn encode_nonzero_u128(buf: &mut BufU128, value: NonZeroU128) -> usize {
+	encode_u128(buf, value.get() - 1)
+}
+
+/// Returns the length of a `NonZeroU128` encoded by [`encode_nonzero_u128`].
+///
+/// # Examples
+///
+/// ```
+/// # use core::num::NonZeroU128;
+/// let value = NonZeroU128::new(129).unwrap();
+/// assert_eq!(vu128::encoded_len_nonzero_u128(value), 2);
+/// ```
+#[must_use]
+pub const fn encoded_len_nonzero_u128(value: NonZeroU128) -> usize {
+	encoded_len_u128(value.get() - 1)
+}
+
+/// Decodes a `NonZeroU128` from a buffer, returning the value and encoded
+/// length.
+///
+/// An error is returned if the encoded value is `u128::MAX`, which has no
+/// corresponding `NonZeroU128`.
+///
+/// # Examples
+///
+/// ```
+/// # use core::num::NonZeroU128;
+/// let mut buf = [0u8; 17];
+/// let value = NonZeroU128::new(128).unwrap();
+/// let encoded_len = vu128::encode_nonzero_u128(&mut buf, value);
+/// assert_eq!(vu128::decode_nonzero_u128(&buf), Ok((value, encoded_len)));
+/// ```
+#[inline]
+pub fn decode_nonzero_u128(
+	buf: &BufU128,
+) -> Result<(NonZeroU128, usize), DecodeError> {
+	let (value, len) = decode_u128(buf);
+	nonzero_decoded(NonZeroU128::new(value.wrapping_add(1)), len)
+}
+
+/// Decodes a `NonZeroU128` from a slice, returning the value and encoded
+/// length.
+///
+/// Unlike [`decode_nonzero_u128`], the input may be of any length. An error is
+/// returned if the slice is shorter than the encoded length indicated by its
+/// first byte, or if the encoded value has no corresponding `NonZeroU128`.
+///
+/// # Examples
+///
+/// ```
+/// # use core::num::NonZeroU128;
+/// let value = NonZeroU128::new(128).unwrap();
+/// let decoded = vu128::decode_nonzero_u128_from_slice(&[0x7F]);
+/// assert_eq!(decoded, Ok((value, 1)));
+/// ```
+#[inline]
+pub fn decode_nonzero_u128_from_slice(
+	buf: &[u8],
+) -> Result<(NonZeroU128, usize), DecodeError> {
+	let (value, len) = decode_u128_from_slice(buf)?;
+	nonzero_decoded(NonZeroU128::new(value.wrapping_add(1)), len)
+}
+
+/// Encodes a `char` into a buffer, returning the encoded length.
+///
+/// Characters are encoded as their Unicode scalar value, so ASCII characters
+/// are encoded in a single byte and all others in at most three bytes.
+///
+/// The contents of the buffer beyond the returned length are unspecified.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 3];
+/// let encoded_len = vu128::encode_char(&mut buf, 'a');
+/// assert_eq!(&buf[..encoded_len], b"a");
+///
+/// let encoded_len = vu128::encode_char(&mut buf, '\u{1F980}');
+/// assert_eq!(&buf[..encoded_len], &[0xC0, 0xCC, 0x0F]);
+/// ```
+#[inline]
+#[must_use]
+pub fn encode_char(buf: &mut BufChar, value: char) -> usize {
+	encode_u21_raw(as_uninit_mut(buf), value as u32)
+}
+
+/// Returns the length of a `char` encoded by [`encode_char`].
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 3];
+/// let encoded_len = vu128::encode_char(&mut buf, '\u{1F980}');
+/// assert_eq!(vu128::encoded_len_char('\u{1F980}'), encoded_len);
+/// ```
+#[must_use]
+pub const fn encoded_len_char(value: char) -> usize {
+	encoded_len_u32(value as u32)
+}
+
+/// Decodes a `char` from a buffer, returning the value and encoded length.
+///
+/// An error is returned if the encoded value is not a Unicode scalar value,
+/// such as a surrogate code point or a value greater than `0x10FFFF`.
+///
+/// # Examples
+///
+/// ```
+/// # use vu128::DecodeErrorKind;
+/// let mut buf = [0u8; 3];
+/// let encoded_len = vu128::encode_char(&mut buf, '\u{1F980}');
+/// assert_eq!(vu128::decode_char(&buf), Ok(('\u{1F980}', encoded_len)));
+///
+/// let err = vu128::decode_char(&[0xC0, 0xC0, 0x06]).unwrap_err();
+/// assert_eq!(err.kind(), DecodeErrorKind::InvalidChar { value: 0xD800 });
+/// ```
+#[inline]
+pub fn decode_char(buf: &BufChar) -> Result<(char, usize), DecodeError> {
+	check_encoded_len(buf[0], MAX_LEN_CHAR)?;
+	let (value, len) = decode_u32(&widen_buf(buf));
+	char_decoded(value, len)
+}
+
+/// Decodes a `char` from a slice, returning the value and encoded length.
+///
+/// Unlike [`decode_char`], the input may be of any length. An error is
+/// returned if the slice is shorter than the encoded length indicated by its
+/// first byte, if that length is greater than [`MAX_LEN_CHAR`], or if the
+/// encoded value is not a Unicode scalar value.
+///
+/// # Examples
+///
+/// ```
+/// let buf = [0xC0, 0xCC, 0x0F];
+/// assert_eq!(vu128::decode_char_from_slice(&buf), Ok(('\u{1F980}', 3)));
+/// assert!(vu128::decode_char_from_slice(&buf[..2]).is_err());
+/// ```
+#[inline]
+pub fn decode_char_from_slice(
+	buf: &[u8],
+) -> Result<(char, usize), DecodeError> {
+	let (value, len) =
+		decode_unsigned_from_slice(buf, MAX_LEN_CHAR, decode_u32_exact)?;
+	char_decoded(value, len)
+}
+
+/// Types that can be encoded as `vu128` values.
+///
+/// This trait allows generic code to be written over all supported types. It
+/// is implemented by each type with `encode_*` and `decode_*` functions in this
+/// crate, and cannot be implemented for other types.
+///
+/// # Examples
+///
+/// ```
+/// use vu128::Vu128;
+///
+/// fn encode_all<T: Vu128>(values: &[T], out: &mut Vec<u8>) {
+///     for &value in values {
+///         let mut buf = T::Buf::default();
+///         let len = T::encode(&mut buf, value);
+///         out.extend_from_slice(&buf.as_ref()[..len]);
+///     }
+/// }
+///
+/// let mut out = Vec::new();
+/// encode_all(&[1u32, 12345], &mut out);
+/// encode_all(&[-1i64], &mut out);
+/// assert_eq!(out, &[0x01, 0xB9, 0xC0, 0x01]);
+/// ```
+pub trait Vu128: Copy + sealed::Sealed {
+	/// A buffer large enough to hold any encoded value of this type.
+	///
+	/// This is always an array of [`MAX_LEN`](Self::MAX_LEN) bytes.
+	type Buf: AsRef<[u8]> + AsMut<[u8]> + Copy + Default + fmt::Debug;
+
+	/// The maximum encoded length of a value of this type.
+	const MAX_LEN: usize;
+
+	/// Encodes a value into a buffer, returning the encoded length.
+	///
+	/// The contents of the buffer beyond the returned length are unspecified.
+	fn encode(buf: &mut Self::Buf, value: Self) -> usize;
+
+	/// Decodes a value from a buffer, returning the value and encoded length.
+	fn decode(buf: &Self::Buf) -> (Self, usize);
+
+	/// Decodes a value from a slice, returning the value and encoded length.
+	///
+	/// An error is returned if the slice is shorter than the encoded length
+	/// indicated by its first byte, or if the encoded value does not fit in
+	/// this type.
+	fn decode_from_slice(buf: &[u8]) -> Result<(Self, usize), DecodeError>;
+
+	/// Returns the length of a value encoded by [`Vu128::encode`].
+	fn encoded_len(value: Self) -> usize;
+}
+
+macro_rules! impl_vu128 {
+	($t:ident {
+		Buf: $buf:ident,
+		MAX_LEN: $max_len:ident,
+		encode: $encode_fn:ident,
+		decode: $decode_fn:ident,
+		decode_from_slice: $decode_from_slice_fn:ident,
+		encoded_len: $encoded_len_fn:ident,
+		encode_uninit: $encode_uninit_fn:ident,
+	}) => {
+		impl sealed::Sealed for $t {
+			#[inline]
+			unsafe fn encode_to_ptr(ptr: *mut u8, value: $t) -> usize {
+				let ptr = ptr.cast::<[MaybeUninit<u8>; $max_len]>();
+				// SAFETY: `[MaybeUninit<u8>; N]` has an alignment of 1 and no
+				// validity invariant. The caller ensures that `ptr` is valid
+				// for writes of `$max_len` bytes that are not otherwise
+				// accessed during the call, so it can be viewed as a unique
+				// reference to an uninitialized array.
+				let buf = unsafe { &mut *ptr };
+				$encode_uninit_fn(buf, value).len()
+			}
+		}
+
+		impl Vu128 for $t {
+			type Buf = $buf;
+
+			const MAX_LEN: usize = $max_len;
+
+			#[inline]
+			fn encode(buf: &mut $buf, value: $t) -> usize {
+				$encode_fn(buf, value)
+			}
+
+			#[inline]
+			fn decode(buf: &$buf) -> ($t, usize) {
+				$decode_fn(buf)
+			}
+
+			#[inline]
+			fn decode_from_slice(
+				buf: &[u8],
+			) -> Result<($t, usize), DecodeError> {
+				$decode_from_slice_fn(buf)
+			}
+
+			#[inline]
+			fn encoded_len(value: $t) -> usize {
+				$encoded_len_fn(value)
+			}
+		}
+	};
+}
+
+impl_vu128!(u8 {
+	Buf: BufU8,
+	MAX_LEN: MAX_LEN_U8,
+	encode: encode_u8,
+	decode: decode_u8,
+	decode_from_slice: decode_u8_from_slice,
+	encoded_len: encoded_len_u8,
+	encode_uninit: encode_u8_uninit,
+});
+
+impl_vu128!(u16 {
+	Buf: BufU16,
+	MAX_LEN: MAX_LEN_U16,
+	encode: encode_u16,
+	decode: decode_u16,
+	decode_from_slice: decode_u16_from_slice,
+	encoded_len: encoded_len_u16,
+	encode_uninit: encode_u16_uninit,
+});
+
+impl_vu128!(u32 {
+	Buf: BufU32,
+	MAX_LEN: MAX_LEN_U32,
+	encode: encode_u32,
+	decode: decode_u32,
+	decode_from_slice: decode_u32_from_slice,
+	encoded_len: encoded_len_u32,
+	encode_uninit: encode_u32_uninit,
+});
+
+impl_vu128!(u64 {
+	Buf: BufU64,
+	MAX_LEN: MAX_LEN_U64,
+	encode: encode_u64,
+	decode: decode_u64,
+	decode_from_slice: decode_u64_from_slice,
+	encoded_len: encoded_len_u64,
+	encode_uninit: encode_u64_uninit,
+});
+
+impl_vu128!(u128 {
+	Buf: BufU128,
+	MAX_LEN: MAX_LEN_U128,
+	encode: encode_u128,
+	decode: decode_u128,
+	decode_from_slice: decode_u128_from_slice,
+	encoded_len: encoded_len_u128,
+	encode_uninit: encode_u128_uninit,
+});
+
+impl_vu128!(usize {
+	Buf: BufU64,
+	MAX_LEN: MAX_LEN_U64,
+	encode: encode_usize,
+	decode: decode_usize,
+	decode_from_slice: decode_usize_from_slice,
+	encoded_len: encoded_len_usize,
+	encode_uninit: encode_usize_uninit,
+});
+
+impl_vu128!(i8 {
+	Buf: BufU8,
+	MAX_LEN: MAX_LEN_U8,
+	encode: encode_i8,
+	decode: decode_i8,
+	decode_from_slice: decode_i8_from_slice,
+	encoded_len: encoded_len_i8,
+	encode_uninit: encode_i8_uninit,
+});
+
+impl_vu128!(i16 {
+	Buf: BufU16,
+	MAX_LEN: MAX_LEN_U16,
+	encode: encode_i16,
+	decode: decode_i16,
+	decode_from_slice: decode_i16_from_slice,
+	encoded_len: encoded_len_i16,
+	encode_uninit: encode_i16_uninit,
+});
+
+impl_vu128!(i32 {
+	Buf: BufU32,
+	MAX_LEN: MAX_LEN_U32,
+	encode: encode_i32,
+	decode: decode_i32,
+	decode_from_slice: decode_i32_from_slice,
+	encoded_len: encoded_len_i32,
+	encode_uninit: encode_i32_uninit,
+});
+
+impl_vu128!(i64 {
+	Buf: BufU64,
+	MAX_LEN: MAX_LEN_U64,
+	encode: encode_i64,
+	decode: decode_i64,
+	decode_from_slice: decode_i64_from_slice,
+	encoded_len: encoded_len_i64,
+	encode_uninit: encode_i64_uninit,
+});
+
+impl_vu128!(i128 {
+	Buf: BufU128,
+	MAX_LEN: MAX_LEN_U128,
+	encode: encode_i128,
+	decode: decode_i128,
+	decode_from_slice: decode_i128_from_slice,
+	encoded_len: encoded_len_i128,
+	encode_uninit: encode_i128_uninit,
+});
+
+impl_vu128!(isize {
+	Buf: BufU64,
+	MAX_LEN: MAX_LEN_U64,
+	encode: encode_isize,
+	decode: decode_isize,
+	decode_from_slice: decode_isize_from_slice,
+	encoded_len: encoded_len_isize,
+	encode_uninit: encode_isize_uninit,
+});
+
+impl_vu128!(f32 {
+	Buf: BufU32,
+	MAX_LEN: MAX_LEN_U32,
+	encode: encode_f32,
+	decode: decode_f32,
+	decode_from_slice: decode_f32_from_slice,
+	encoded_len: encoded_len_f32,
+	encode_uninit: encode_f32_uninit,
+});
+
+impl_vu128!(f64 {
+	Buf: BufU64,
+	MAX_LEN: MAX_LEN_U64,
+	encode: encode_f64,
+	decode: decode_f64,
+	decode_from_slice: decode_f64_from_slice,
+	encoded_len: encoded_len_f64,
+	encode_uninit: encode_f64_uninit,
+});
+
+/// Returns `true` if `buf` is the canonical encoding of a `u32`.
+///
+/// A canonical encoding is the one produced by [`encode_u32`]. Returns
+/// `false` if `buf` is over-long, contains trailing bytes, or does not
+/// contain a valid `u32`.
+///
+/// # Examples
+///
+/// ```
+/// assert!(vu128::is_canonical_u32(&[0xB9, 0xC0]));
+/// assert!(!vu128::is_canonical_u32(&[0xF1, 0x39, 0x30]));
+/// assert!(!vu128::is_canonical_u32(&[0xB9, 0xC0, 0x00]));
+/// ```
+#[must_use]
+pub fn is_canonical_u32(buf: &[u8]) -> bool {
+	match decode_u32_canonical(buf) {
+		Ok((_, len)) => len == buf.len(),
+		Err(_) => false,
+	}
+}
+
+/// Returns `true` if `buf` is the canonical encoding of a `u64`.
+///
+/// A canonical encoding is the one produced by [`encode_u64`]. Returns
+/// `false` if `buf` is over-long, contains trailing bytes, or does not
+/// contain a valid `u64`.
+///
+/// # Examples
+///
+/// ```
+/// assert!(vu128::is_canonical_u64(&[0xB9, 0xC0]));
+/// assert!(!vu128::is_canonical_u64(&[0xF1, 0x39, 0x30]));
+/// assert!(!vu128::is_canonical_u64(&[0xB9, 0xC0, 0x00]));
+/// ```
+#[must_use]
+pub fn is_canonical_u64(buf: &[u8]) -> bool {
+	match decode_u64_canonical(buf) {
+		Ok((_, len)) => len == buf.len(),
+		Err(_) => false,
+	}
+}
+
+/// Returns `true` if `buf` is the canonical encoding of a `u128`.
+///
+/// A canonical encoding is the one produced by [`encode_u128`]. Returns
+/// `false` if `buf` is over-long, contains trailing bytes, or does not
+/// contain a valid `u128`.
+///
+/// # Examples
+///
+/// ```
+/// assert!(vu128::is_canonical_u128(&[0xB9, 0xC0]));
+/// assert!(!vu128::is_canonical_u128(&[0xF1, 0x39, 0x30]));
+/// assert!(!vu128::is_canonical_u128(&[0xB9, 0xC0, 0x00]));
+/// ```
+#[must_use]
+pub fn is_canonical_u128(buf: &[u8]) -> bool {
+	match decode_u128_canonical(buf) {
+		Ok((_, len)) => len == buf.len(),
+		Err(_) => false,
+	}
+}
+
+/// Rewrites a buffer of concatenated values into canonical form, returning
+/// the canonical length.
+///
+/// Each over-long encoding in `buf` is replaced by the encoding produced by
+/// the `encode_*` functions, and the following values are moved down to fill
+/// the gap. The canonical form of a value does not depend on its type, so
+/// `buf` may contain values of any type.
+///
+/// If an error is returned, its offset is the position of the invalid value
+/// in the original buffer, and the contents of `buf` are unspecified.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0xF1, 0x39, 0x30, 0x81, 0x00, 0x05];
+/// let len = vu128::canonicalize(&mut buf).unwrap();
+/// assert_eq!(&buf[..len], &[0xB9, 0xC0, 0x01, 0x05]);
+/// ```
+pub fn canonicalize(buf: &mut [u8]) -> Result<usize, DecodeError> {
+	let mut read = 0;
+	let mut write = 0;
+	while read < buf.len() {
+		let (value, len) = match decode_u128_from_slice(&buf[read..]) {
+			Ok(decoded) => decoded,
+			Err(err) => return Err(err.offset_by(read)),
+		};
+		let mut tmp = [MaybeUninit::uninit(); MAX_LEN_U128];
+		let canonical = encode_u128_uninit(&mut tmp, value);
+		buf[write..write + canonical.len()].copy_from_slice(canonical);
+		read += len;
+		write += canonical.len();
+	}
+	Ok(write)
+}
+
+/// Decodes values from a buffer, tracking the current position.
+///
+/// Values are decoded directly from the buffer when enough bytes remain to
+/// hold the largest encoding of the requested type, and otherwise via a
+/// zero-padded copy of the remaining bytes. Errors report the absolute
+/// offset of the value that could not be decoded.
+///
+/// # Examples
+///
+/// ```
+/// let buf = [0xB9, 0xC0, 0x01, b'h', b'i'];
+/// let mut decoder = vu128::Decoder::new(&buf);
+///
+/// assert_eq!(decoder.read_u32()?, 12345);
+/// assert_eq!(decoder.read_i64()?, -1);
+/// assert_eq!(decoder.read_bytes(2)?, b"hi");
+/// assert_eq!(decoder.remaining(), 0);
+///
+/// let err = decoder.read_u32().unwrap_err();
+/// assert_eq!(err.offset(), 5);
+/// # Ok::<(), vu128::DecodeError>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct Decoder<'a> {
+	buf: &'a [u8],
+	pos: usize,
+}
+
+impl<'a> Decoder<'a> {
+	/// Creates a new `Decoder` that reads from the start of `buf`.
+	#[inline]
+	#[must_use]
+	pub fn new(buf: &'a [u8]) -> Decoder<'a> {
+		Decoder { buf, pos: 0 }
+	}
+
+	/// Returns the number of bytes read so far.
+	#[inline]
+	#[must_use]
+	pub fn position(&self) -> usize {
+		self.pos
+	}
+
+	/// Returns the number of bytes remaining in the buffer.
+	#[inline]
+	#[must_use]
+	pub fn remaining(&self) -> usize {
+		self.buf.len() - self.pos
+	}
+
+	/// Reads `len` raw bytes from the buffer.
+	///
+	/// An error is returned if fewer than `len` bytes remain, in which case
+	/// the position is not advanced.
+	pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+		let available = self.remaining();
+		if len > available {
+			let kind = DecodeErrorKind::Truncated {
+				needed: len,
+				available,
+			};
+			return Err(DecodeError::new(kind).offset_by(self.pos));
+		}
+		let start = self.pos;
+		self.pos += len;
+		Ok(&self.buf[start..self.pos])
+	}
+
+	/// Decodes a value of any supported type from the buffer.
+	///
+	/// An error is returned if the value is truncated or does not fit in the
+	/// requested type, in which case the position is not advanced.
+	#[inline]
+	pub fn read_vu128<T: Vu128>(&mut self) -> Result<T, DecodeError> {
+		match T::decode_from_slice(&self.buf[self.pos..]) {
+			Ok((value, len)) => {
+				self.pos += len;
+				Ok(value)
+			},
+			Err(err) => Err(err.offset_by(self.pos)),
+		}
+	}
+
+	/// Decodes a `u8` from the buffer.
+	///
+	/// An error is returned if the value is truncated or does not fit in
+	/// a `u8`, in which case the position is not advanced.
+	#[inline]
+	pub fn read_u8(&mut self) -> Result<u8, DecodeError> {
+		self.read_vu128()
+	}
+
+	/// Decodes a `u16` from the buffer.
+	///
+	/// An error is returned if the value is truncated or does not fit in
+	/// a `u16`, in which case the position is not advanced.
+	#[inline]
+	pub fn read_u16(&mut self) -> Result<u16, DecodeError> {
+		self.read_vu128()
+	}
+
+	/// Decodes a `u32` from the buffer.
+	///
+	/// An error is returned if the value is truncated or does not fit in
+	/// a `u32`, in which case the position is not advanced.
+	#[inline]
+	pub fn read_u32(&mut self) -> Result<u32, DecodeError> {
+		self.read_vu128()
+	}
+
+	/// Decodes a `u64` from the buffer.
+	///
+	/// An error is returned if the value is truncated or does not fit in
+	/// a `u64`, in which case the position is not advanced.
+	#[inline]
+	pub fn read_u64(&mut self) -> Result<u64, DecodeError> {
+		self.read_vu128()
+	}
+
+	/// Decodes a `u128` from the buffer.
+	///
+	/// An error is returned if the value is truncated or does not fit in
+	/// a `u128`, in which case the position is not advanced.
+	#[inline]
+	pub fn read_u128(&mut self) -> Result<u128, DecodeError> {
+		self.read_vu128()
+	}
+
+	/// Decodes a `usize` from the buffer.
+	///
+	/// An error is returned if the value is truncated or does not fit in
+	/// a `usize`, in which case the position is not advanced.
+	#[inline]
+	pub fn read_usize(&mut self) -> Result<usize, DecodeError> {
+		self.read_vu128()
+	}
+
+	/// Decodes an `i8` from the buffer.
+	///
+	/// An error is returned if the value is truncated or does not fit in
+	/// an `i8`, in which case the position is not advanced.
+	#[inline]
+	pub fn read_i8(&mut self) -> Result<i8, DecodeError> {
+		self.read_vu128()
+	}
+
+	/// Decodes an `i16` from the buffer.
+	///
+	/// An error is returned if the value is truncated or does not fit in
+	/// an `i16`, in which case the position is not advanced.
+	#[inline]
+	pub fn read_i16(&mut self) -> Result<i16, DecodeError> {
+		self.read_vu128()
+	}
+
+	/// Decodes an `i32` from the buffer.
+	///
+	/// An error is returned if the value is truncated or does not fit in
+	/// an `i32`, in which case the position is not advanced.
+	#[inline]
+	pub fn read_i32(&mut self) -> Result<i32, DecodeError> {
+		self.read_vu128()
+	}
+
+	/// Decodes an `i64` from the buffer.
+	///
+	/// An error is returned if the value is truncated or does not fit in
+	/// an `i64`, in which case the position is not advanced.
+	#[inline]
+	pub fn read_i64(&mut self) -> Result<i64, DecodeError> {
+		self.read_vu128()
+	}
+
+	/// Decodes an `i128` from the buffer.
+	///
+	/// An error is returned if the value is truncated or does not fit in
+	/// an `i128`, in which case the position is not advanced.
+	#[inline]
+	pub fn read_i128(&mut self) -> Result<i128, DecodeError> {
+		self.read_vu128()
+	}
+
+	/// Decodes an `isize` from the buffer.
+	///
+	/// An error is returned if the value is truncated or does not fit in
+	/// an `isize`, in which case the position is not advanced.
+	#[inline]
+	pub fn read_isize(&mut self) -> Result<isize, DecodeError> {
+		self.read_vu128()
+	}
+
+	/// Decodes an `f32` from the buffer.
+	///
+	/// An error is returned if the value is truncated or does not fit in
+	/// an `f32`, in which case the position is not advanced.
+	#[inline]
+	pub fn read_f32(&mut self) -> Result<f32, DecodeError> {
+		self.read_vu128()
+	}
+
+	/// Decodes an `f64` from the buffer.
+	///
+	/// An error is returned if the value is truncated or does not fit in
+	/// an `f64`, in which case the position is not advanced.
+	#[inline]
+	pub fn read_f64(&mut self) -> Result<f64, DecodeError> {
+		self.read_vu128()
+	}
+}
+
+/// An iterator over the values encoded in a buffer.
+///
+/// The buffer must contain only concatenated `vu128` values. If a value
+/// cannot be decoded, the error is returned and iteration stops.
+///
+/// # Examples
+///
+/// ```
+/// let buf = [0x01, 0xB9, 0xC0, 0xF3];
+/// let mut values = vu128::iter_u32(&buf);
+///
+/// assert_eq!(values.next(), Some(Ok(1)));
+/// assert_eq!(values.next(), Some(Ok(12345)));
+///
+/// let err = values.next().unwrap().unwrap_err();
+/// assert_eq!(err.offset(), 3);
+/// assert_eq!(values.next(), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DecodeIter<'a, T> {
+	decoder: Decoder<'a>,
+	failed: bool,
+	_marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: Vu128> DecodeIter<'a, T> {
+	/// Creates a new `DecodeIter` that decodes values from `buf`.
+	#[inline]
+	#[must_use]
+	pub fn new(buf: &'a [u8]) -> DecodeIter<'a, T> {
+		DecodeIter {
+			decoder: Decoder::new(buf),
+			failed: false,
+			_marker: PhantomData,
+		}
+	}
+
+	/// Returns the number of bytes decoded so far.
+	#[inline]
+	#[must_use]
+	pub fn position(&self) -> usize {
+		self.decoder.position()
+	}
+}
+
+impl<T: Vu128> Iterator for DecodeIter<'_, T> {
+	type Item = Result<T, DecodeError>;
+
+	#[inline]
+	fn next(&mut self) -> Option<Result<T, DecodeError>> {
+		if self.failed || self.decoder.remaining() == 0 {
+			return None;
+		}
+		let result = self.decoder.read_vu128();
+		self.failed = result.is_err();
+		Some(result)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		if self.failed {
+			return (0, Some(0));
+		}
+		let remaining = self.decoder.remaining();
+		(remaining.min(1), Some(remaining))
+	}
+}
+
+impl<T: Vu128> FusedIterator for DecodeIter<'_, T> {}
+
+/// Returns an iterator over the `u8` values encoded in a buffer.
+///
+/// See [`DecodeIter`] for details.
+#[inline]
+#[must_use]
+pub fn iter_u8(buf: &[u8]) -> DecodeIter<'_, u8> {
+	DecodeIter::new(buf)
+}
+
+/// Returns an iterator over the `u16` values encoded in a buffer.
+///
+/// See [`DecodeIter`] for details.
+#[inline]
+#[must_use]
+pub fn iter_u16(buf: &[u8]) -> DecodeIter<'_, u16> {
+	DecodeIter::new(buf)
+}
+
+/// Returns an iterator over the `u32` values encoded in a buffer.
+///
+/// See [`DecodeIter`] for details.
+#[inline]
+#[must_use]
+pub fn iter_u32(buf: &[u8]) -> DecodeIter<'_, u32> {
+	DecodeIter::new(buf)
+}
+
+/// Returns an iterator over the `u64` values encoded in a buffer.
+///
+/// See [`DecodeIter`] for details.
+#[inline]
+#[must_use]
+pub fn iter_u64(buf: &[u8]) -> DecodeIter<'_, u64> {
+	DecodeIter::new(buf)
+}
+
+/// Returns an iterator over the `u128` values encoded in a buffer.
+///
+/// See [`DecodeIter`] for details.
+#[inline]
+#[must_use]
+pub fn iter_u128(buf: &[u8]) -> DecodeIter<'_, u128> {
+	DecodeIter::new(buf)
+}
+
+/// Returns an iterator over the `usize` values encoded in a buffer.
+///
+/// See [`DecodeIter`] for details.
+#[inline]
+#[must_use]
+pub fn iter_usize(buf: &[u8]) -> DecodeIter<'_, usize> {
+	DecodeIter::new(buf)
+}
+
+/// Returns an iterator over the `i8` values encoded in a buffer.
+///
+/// See [`DecodeIter`] for details.
+#[inline]
+#[must_use]
+pub fn iter_i8(buf: &[u8]) -> DecodeIter<'_, i8> {
+	DecodeIter::new(buf)
+}
+
+/// Returns an iterator over the `i16` values encoded in a buffer.
+///
+/// See [`DecodeIter`] for details.
+#[inline]
+#[must_use]
+pub fn iter_i16(buf: &[u8]) -> DecodeIter<'_, i16> {
+	DecodeIter::new(buf)
+}
+
+/// Returns an iterator over the `i32` values encoded in a buffer.
+///
+/// See [`DecodeIter`] for details.
+#[inline]
+#[must_use]
+pub fn iter_i32(buf: &[u8]) -> DecodeIter<'_, i32> {
+	DecodeIter::new(buf)
+}
+
+/// Returns an iterator over the `i64` values encoded in a buffer.
+///
+/// See [`DecodeIter`] for details.
+#[inline]
+#[must_use]
+pub fn iter_i64(buf: &[u8]) -> DecodeIter<'_, i64> {
+	DecodeIter::new(buf)
+}
+
+/// Returns an iterator over the `i128` values encoded in a buffer.
+///
+/// See [`DecodeIter`] for details.
+#[inline]
+#[must_use]
+pub fn iter_i128(buf: &[u8]) -> DecodeIter<'_, i128> {
+	DecodeIter::new(buf)
+}
+
+/// Returns an iterator over the `isize` values encoded in a buffer.
+///
+/// See [`DecodeIter`] for details.
+#[inline]
+#[must_use]
+pub fn iter_isize(buf: &[u8]) -> DecodeIter<'_, isize> {
+	DecodeIter::new(buf)
+}
+
+/// Returns an iterator over the `f32` values encoded in a buffer.
+///
+/// See [`DecodeIter`] for details.
+#[inline]
+#[must_use]
+pub fn iter_f32(buf: &[u8]) -> DecodeIter<'_, f32> {
+	DecodeIter::new(buf)
+}
+
+/// Returns an iterator over the `f64` values encoded in a buffer.
+///
+/// See [`DecodeIter`] for details.
+#[inline]
+#[must_use]
+pub fn iter_f64(buf: &[u8]) -> DecodeIter<'_, f64> {
+	DecodeIter::new(buf)
+}
+
+/// Encodes a slice of `u32` values into a buffer, returning the encoded
+/// length.
+///
+/// The values are encoded consecutively, as if by calling [`encode_u32`] for
+/// each value, but without an intermediate buffer. The contents of `out`
+/// beyond the returned length are unspecified.
+///
+/// # Panics
+///
+/// Panics if `out` is too small to hold the encoded values. The length is
+/// checked before anything is written, so `out` is left unmodified by a
+/// panic. A buffer of `values.len() * MAX_LEN_U32` bytes is always large
+/// enough; for a smaller buffer the exact encoded length is computed first.
+///
+/// # Examples
+///
+/// ```
+/// let mut out = [0u8; 2 * vu128::MAX_LEN_U32];
+/// let encoded_len = vu128::encode_u32_slice(&[1, 12345], &mut out);
+/// assert_eq!(&out[..encoded_len], &[0x01, 0xB9, 0xC0]);
+/// ```
+#[inline]
+pub fn encode_u32_slice(values: &[u32], out: &mut [u8]) -> usize {
+	encode_slice(values, out)
+}
+
+/// Encodes a slice of `u64` values into a buffer, returning the encoded
+/// length.
+///
+/// The values are encoded consecutively, as if by calling [`encode_u64`] for
+/// each value, but without an intermediate buffer. The contents of `out`
+/// beyond the returned length are unspecified.
+///
+/// # Panics
+///
+/// Panics if `out` is too small to hold the encoded values. The length is
+/// checked before anything is written, so `out` is left unmodified by a
+/// panic. A buffer of `values.len() * MAX_LEN_U64` bytes is always large
+/// enough; for a smaller buffer the exact encoded length is computed first.
+///
+/// # Examples
+///
+/// ```
+/// let mut out = [0u8; 2 * vu128::MAX_LEN_U64];
+/// let encoded_len = vu128::encode_u64_slice(&[1, 12345], &mut out);
+/// assert_eq!(&out[..encoded_len], &[0x01, 0xB9, 0xC0]);
+/// ```
+#[inline]
+pub fn encode_u64_slice(values: &[u64], out: &mut [u8]) -> usize {
+	encode_slice(values, out)
+}
+
+/// Encodes a slice of `u128` values into a buffer, returning the encoded
+/// length.
+///
+/// The values are encoded consecutively, as if by calling [`encode_u128`] for
+/// each value, but without an intermediate buffer. The contents of `out`
+/// beyond the returned length are unspecified.
+///
+/// # Panics
+///
+/// Panics if `out` is too small to hold the encoded values. The length is
+/// checked before anything is written, so `out` is left unmodified by a
+/// panic. A buffer of `values.len() * MAX_LEN_U128` bytes is always large
+/// enough; for a smaller buffer the exact encoded length is computed first.
+///
+/// # Examples
+///
+/// ```
+/// let mut out = [0u8; 2 * vu128::MAX_LEN_U128];
+/// let encoded_len = vu128::encode_u128_slice(&[1, 12345], &mut out);
+/// assert_eq!(&out[..encoded_len], &[0x01, 0xB9, 0xC0]);
+/// ```
+#[inline]
+pub fn encode_u128_slice(values: &[u128], out: &mut [u8]) -> usize {
+	encode_slice(values, out)
+}
+
+/// Encodes a slice of `i32` values into a buffer, returning the encoded
+/// length.
+///
+/// The values are encoded consecutively, as if by calling [`encode_i32`] for
+/// each value, but without an intermediate buffer. The contents of `out`
+/// beyond the returned length are unspecified.
+///
+/// # Panics
+///
+/// Panics if `out` is too small to hold the encoded values. The length is
+/// checked before anything is written, so `out` is left unmodified by a
+/// panic. A buffer of `values.len() * MAX_LEN_U32` bytes is always large
+/// enough; for a smaller buffer the exact encoded length is computed first.
+///
+/// # Examples
+///
+/// ```
+/// let mut out = [0u8; 2 * vu128::MAX_LEN_U32];
+/// let encoded_len = vu128::encode_i32_slice(&[-1, 1], &mut out);
+/// assert_eq!(&out[..encoded_len], &[0x01, 0x02]);
+/// ```
+#[inline]
+pub fn encode_i32_slice(values: &[i32], out: &mut [u8]) -> usize {
+	encode_slice(values, out)
+}
+
+/// Encodes a slice of `i64` values into a buffer, returning the encoded
+/// length.
+///
+/// The values are encoded consecutively, as if by calling [`encode_i64`] for
+/// each value, but without an intermediate buffer. The contents of `out`
+/// beyond the returned length are unspecified.
+///
+/// # Panics
+///
+/// Panics if `out` is too small to hold the encoded values. The length is
+/// checked before anything is written, so `out` is left unmodified by a
+/// panic. A buffer of `values.len() * MAX_LEN_U64` bytes is always large
+/// enough; for a smaller buffer the exact encoded length is computed first.
+///
+/// # Examples
+///
+/// ```
+/// let mut out = [0u8; 2 * vu128::MAX_LEN_U64];
+/// let encoded_len = vu128::encode_i64_slice(&[-1, 1], &mut out);
+/// assert_eq!(&out[..encoded_len], &[0x01, 0x02]);
+/// ```
+#[inline]
+pub fn encode_i64_slice(values: &[i64], out: &mut [u8]) -> usize {
+	encode_slice(values, out)
+}
+
+/// Encodes a slice of `i128` values into a buffer, returning the encoded
+/// length.
+///
+/// The values are encoded consecutively, as if by calling [`encode_i128`] for
+/// each value, but without an intermediate buffer. The contents of `out`
+/// beyond the returned length are unspecified.
+///
+/// # Panics
+///
+/// Panics if `out` is too small to hold the encoded values. The length is
+/// checked before anything is written, so `out` is left unmodified by a
+/// panic. A buffer of `values.len() * MAX_LEN_U128` bytes is always large
+/// enough; for a smaller buffer the exact encoded length is computed first.
+///
+/// # Examples
+///
+/// ```
+/// let mut out = [0u8; 2 * vu128::MAX_LEN_U128];
+/// let encoded_len = vu128::encode_i128_slice(&[-1, 1], &mut out);
+/// assert_eq!(&out[..encoded_len], &[0x01, 0x02]);
+/// ```
+#[inline]
+pub fn encode_i128_slice(values: &[i128], out: &mut [u8]) -> usize {
+	encode_slice(values, out)
+}
+
+/// Decodes `u32` values from a buffer into a slice, returning the number of
+/// bytes consumed and values produced.
+///
+/// Values are decoded until `out` is full or the input is exhausted. If the
+/// input ends partway through a value, decoding stops before that value, so
+/// that it can be decoded once the rest of its bytes are available.
+///
+/// An error is returned if a value does not fit in a `u32`. The values decoded
+/// before the error are written to `out`, and their count is reported by
+/// [`DecodeSliceError::produced`].
+///
+/// With the `std` feature enabled on x86_64, values are decoded several at a
+/// time using SSE4.1 or AVX2 instructions if the CPU supports them.
+///
+/// # Examples
+///
+/// ```
+/// let mut out = [0; 4];
+/// let input = [0x01, 0xB9, 0xC0, 0xF3];
+/// let decoded = vu128::decode_u32_slice(&input, &mut out);
+/// assert_eq!(decoded, Ok((3, 2)));
+/// assert_eq!(&out[..2], &[1, 12345]);
+/// ```
+#[inline]
+pub fn decode_u32_slice(
+	input: &[u8],
+	out: &mut [u32],
+) -> Result<(usize, usize), DecodeSliceError> {
+	#[cfg(all(feature = "std", target_arch = "x86_64"))]
+	if let Some(kernel) = x86::detect_kernel() {
+		// SAFETY: `kernel` was returned by `detect_kernel()`, so the target
+		// features it requires are supported by this CPU.
+		return unsafe { x86::decode_u32_slice(input, out, kernel) };
+	}
+	decode_slice(input, out, decode_u32_exact)
+}
+
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+#[doc(hidden)]
+pub use x86::Kernel as __X86Kernel;
+
+// Decodes `u32` values with a specific x86 kernel, or returns `None` if the
+// CPU does not support it. This allows tests to check each kernel, and is not
+// part of the public API.
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+#[doc(hidden)]
+#[must_use]
+pub fn __decode_u32_slice_x86(
+	kernel: __X86Kernel,
+	input: &[u8],
+	out: &mut [u32],
+) -> Option<Result<(usize, usize), DecodeSliceError>> {
+	if !x86::is_supported(kernel) {
+		return None;
+	}
+	// SAFETY: The target features required by `kernel` were checked above.
+	Some(unsafe { x86::decode_u32_slice(input, out, kernel) })
+}
+
+/// Decodes `u64` values from a buffer into a slice, returning the number of
+/// bytes consumed and values produced.
+///
+/// Values are decoded until `out` is full or the input is exhausted. If the
+/// input ends partway through a value, decoding stops before that value, so
+/// that it can be decoded once the rest of its bytes are available.
+///
+/// An error is returned if a value does not fit in a `u64`. The values decoded
+/// before the error are written to `out`, and their count is reported by
+/// [`DecodeSliceError::produced`].
+///
+/// # Examples
+///
+/// ```
+/// let mut out = [0; 4];
+/// let input = [0x01, 0xB9, 0xC0, 0xF3];
+/// let decoded = vu128::decode_u64_slice(&input, &mut out);
+/// assert_eq!(decoded, Ok((3, 2)));
+/// assert_eq!(&out[..2], &[1, 12345]);
+/// ```
+#[inline]
+pub fn decode_u64_slice(
+	input: &[u8],
+	out: &mut [u64],
+) -> Result<(usize, usize), DecodeSliceError> {
+	decode_slice(input, out, decode_u64)
+}
+
+/// Decodes `u128` values from a buffer into a slice, returning the number of
+/// bytes consumed and values produced.
+///
+/// Values are decoded until `out` is full or the input is exhausted. If the
+/// input ends partway through a value, decoding stops before that value, so
+/// that it can be decoded once the rest of its bytes are available.
+///
+/// An error is returned if a value does not fit in a `u128`. The values decoded
+/// before the error are written to `out`, and their count is reported by
+/// [`DecodeSliceError::produced`].
+///
+/// # Examples
+///
+/// ```
+/// let mut out = [0; 4];
+/// let input = [0x01, 0xB9, 0xC0, 0xF3];
+/// let decoded = vu128::decode_u128_slice(&input, &mut out);
+/// assert_eq!(decoded, Ok((3, 2)));
+/// assert_eq!(&out[..2], &[1, 12345]);
+/// ```
+#[inline]
+pub fn decode_u128_slice(
+	input: &[u8],
+	out: &mut [u128],
+) -> Result<(usize, usize), DecodeSliceError> {
+	decode_slice(input, out, decode_u128)
+}
+
+/// Decodes `i32` values from a buffer into a slice, returning the number of
+/// bytes consumed and values produced.
+///
+/// Values are decoded until `out` is full or the input is exhausted. If the
+/// input ends partway through a value, decoding stops before that value, so
+/// that it can be decoded once the rest of its bytes are available.
+///
+/// An error is returned if a value does not fit in an `i32`. The values decoded
+/// before the error are written to `out`, and their count is reported by
+/// [`DecodeSliceError::produced`].
+///
+/// # Examples
+///
+/// ```
+/// let mut out = [0; 4];
+/// let input = [0x01, 0x02, 0xF3];
+/// let decoded = vu128::decode_i32_slice(&input, &mut out);
+/// assert_eq!(decoded, Ok((2, 2)));
+/// assert_eq!(&out[..2], &[-1, 1]);
+/// ```
+#[inline]
+pub fn decode_i32_slice(
+	input: &[u8],
+	out: &mut [i32],
+) -> Result<(usize, usize), DecodeSliceError> {
+	decode_slice(input, out, decode_i32_exact)
+}
+
+/// Decodes `i64` values from a buffer into a slice, returning the number of
+/// bytes consumed and values produced.
+///
+/// Values are decoded until `out` is full or the input is exhausted. If the
+/// input ends partway through a value, decoding stops before that value, so
+/// that it can be decoded once the rest of its bytes are available.
+///
+/// An error is returned if a value does not fit in an `i64`. The values decoded
+/// before the error are written to `out`, and their count is reported by
+/// [`DecodeSliceError::produced`].
+///
+/// # Examples
+///
+/// ```
+/// let mut out = [0; 4];
+/// let input = [0x01, 0x02, 0xF3];
+/// let decoded = vu128::decode_i64_slice(&input, &mut out);
+/// assert_eq!(decoded, Ok((2, 2)));
+/// assert_eq!(&out[..2], &[-1, 1]);
+/// ```
+#[inline]
+pub fn decode_i64_slice(
+	input: &[u8],
+	out: &mut [i64],
+) -> Result<(usize, usize), DecodeSliceError> {
+	decode_slice(input, out, decode_i64)
+}
+
+/// Decodes `i128` values from a buffer into a slice, returning the number of
+/// bytes consumed and values produced.
+///
+/// Values are decoded until `out` is full or the input is exhausted. If the
+/// input ends partway through a value, decoding stops before that value, so
+/// that it can be decoded once the rest of its bytes are available.
+///
+/// An error is returned if a value does not fit in an `i128`. The values
+/// decoded before the error are written to `out`, and their count is reported
+/// by [`DecodeSliceError::produced`].
+///
+/// # Examples
+///
+/// ```
+/// let mut out = [0; 4];
+/// let input = [0x01, 0x02, 0xF3];
+/// let decoded = vu128::decode_i128_slice(&input, &mut out);
+/// assert_eq!(decoded, Ok((2, 2)));
+/// assert_eq!(&out[..2], &[-1, 1]);
+/// ```
+#[inline]
+pub fn decode_i128_slice(
+	input: &[u8],
+	out: &mut [i128],
+) -> Result<(usize, usize), DecodeSliceError> {
+	decode_slice(input, out, decode_i128)
+}
+
+/// Returns an iterator over the encoded bytes of a sequence of values.
+///
+/// See [`EncodeIter`] for details.
+///
+/// # Examples
+///
+/// ```
+/// let bytes: Vec<u8> = vu128::encode_iter([1u32, 12345]).collect();
+/// assert_eq!(bytes, &[0x01, 0xB9, 0xC0]);
+///
+/// let mut out = vec![0xFF];
+/// out.extend(vu128::encode_iter((-1i64..=1).rev()));
+/// assert_eq!(out, &[0xFF, 0x02, 0x00, 0x01]);
+/// ```
+#[inline]
+pub fn encode_iter<I>(values: I) -> EncodeIter<I::IntoIter>
+where
+	I: IntoIterator,
+	I::Item: Vu128,
+{
+	EncodeIter::new(values.into_iter())
+}
+
+/// An iterator over the encoded bytes of a sequence of values.
+///
+/// Values are pulled from the underlying iterator and encoded one at a time,
+/// as their bytes are needed. With the `std` feature, `EncodeIter` also
+/// implements `std::io::Read`, which can be used to stream encoded values
+/// into a `std::io::Write` with `std::io::copy`.
+#[derive(Clone, Debug)]
+pub struct EncodeIter<I>
+where
+	I: Iterator,
+	I::Item: Vu128,
+{
+	values: I,
+	buf: <I::Item as Vu128>::Buf,
+	pos: usize,
+	len: usize,
+}
+
+impl<I> EncodeIter<I>
+where
+	I: Iterator,
+	I::Item: Vu128,
+{
+	/// Creates a new `EncodeIter` that encodes the values produced by
+	/// `values`.
+	#[inline]
+	#[must_use]
+	pub fn new(values: I) -> EncodeIter<I> {
+		EncodeIter {
+			values,
+			buf: Default::default(),
+			pos: 0,
+			len: 0,
+		}
+	}
+
+	/// Consumes the `EncodeIter`, returning the underlying iterator.
+	///
+	/// Any encoded bytes of the current value that have not yet been
+	/// returned are discarded.
+	#[inline]
+	#[must_use]
+	pub fn into_inner(self) -> I {
+		self.values
+	}
+}
+
+impl<I> Iterator for EncodeIter<I>
+where
+	I: Iterator,
+	I::Item: Vu128,
+{
+	type Item = u8;
+
+	#[inline]
+	fn next(&mut self) -> Option<u8> {
+		if self.pos == self.len {
+			let value = self.values.next()?;
+			self.len = I::Item::encode(&mut self.buf, value);
+			self.pos = 0;
+		}
+		let b = self.buf.as_ref()[self.pos];
+		self.pos += 1;
+		Some(b)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let pending = self.len - self.pos;
+		let (lower, upper) = self.values.size_hint();
+		let lower = lower.saturating_add(pending);
+		let upper = upper
+			.and_then(|upper| upper.checked_mul(I::Item::MAX_LEN))
+			.and_then(|upper| upper.checked_add(pending));
+		(lower, upper)
+	}
+}
+
+impl<I> FusedIterator for EncodeIter<I>
+where
+	I: FusedIterator,
+	I::Item: Vu128,
+{
+}
+
+#[cfg(feature = "std")]
+impl<I> io::Read for EncodeIter<I>
+where
+	I: Iterator,
+	I::Item: Vu128,
+{
+	fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+		let mut written = 0;
+		while written < out.len() {
+			if self.pos == self.len {
+				let value = match self.values.next() {
+					Some(value) => value,
+					None => break,
+				};
+				let rest = &mut out[written..];
+				if rest.len() >= I::Item::MAX_LEN {
+					// SAFETY: `rest` is valid for writes of `MAX_LEN` bytes,
+					// and neither it nor `out` is used until the call returns.
+					written += unsafe {
+						<I::Item as sealed::Sealed>::encode_to_ptr(
+							rest.as_mut_ptr(),
+							value,
+						)
+					};
+					continue;
+				}
+				self.len = I::Item::encode(&mut self.buf, value);
+				self.pos = 0;
+			}
+			let pending = &self.buf.as_ref()[self.pos..self.len];
+			let count = pending.len().min(out.len() - written);
+			out[written..written + count].copy_from_slice(&pending[..count]);
+			self.pos += count;
+			written += count;
+		}
+		Ok(written)
+	}
+}
+
+/// Encodes values into a caller-provided buffer, tracking the current
+/// position.
+///
+/// Values are encoded directly into the buffer when enough space remains to
+/// hold the largest encoding of the type being written, and otherwise via a
+/// temporary buffer. The contents of the buffer beyond the current position
+/// are unspecified.
+///
+/// Space may also be reserved for values that are not yet known. A common use
+/// is writing a length-prefixed section whose length is only known after its
+/// body has been written. [`Encoder::reserve_u64`] reserves space for the
+/// length, and the returned [`Placeholder`] is filled once the body is
+/// complete.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 4];
+/// let mut encoder = vu128::Encoder::new(&mut buf);
+///
+/// encoder.write_u64(12345)?;
+/// encoder.write_i128(-1)?;
+/// assert_eq!(encoder.position(), 3);
+///
+/// let err = encoder.write_f32(2.5).unwrap_err();
+/// let expect = vu128::EncodeError::BufferFull { needed: 2, available: 1 };
+/// assert_eq!(err, expect);
+///
+/// assert_eq!(encoder.finish(), &[0xB9, 0xC0, 0x01]);
+/// # Ok::<(), vu128::EncodeError>(())
+/// ```
+///
+/// Reserving space for a length prefix:
+///
+/// ```
+/// let mut buf = [0u8; 32];
+/// let mut encoder = vu128::Encoder::new(&mut buf);
+///
+/// let mut placeholder = encoder.reserve_u64(u64::from(u16::MAX))?;
+/// let body_start = encoder.position();
+/// encoder.write_bytes(b"hello")?;
+/// let body_len = encoder.position() - body_start;
+/// placeholder.fill(&mut encoder, body_len as u64)?;
+///
+/// assert_eq!(encoder.finish(), &[0xC5, 0x00, 0x00, b'h', b'e', b'l', b'l', b'o']);
+/// # Ok::<(), vu128::EncodeError>(())
+/// ```
+#[derive(Debug)]
+pub struct Encoder<'a> {
+	buf: &'a mut [u8],
+	pos: usize,
+	// The number of placeholders reserved so far, which is also the sequence
+	// number of the most recent one.
+	reserved: usize,
+	// The sequence number of the innermost unfilled placeholder, or zero.
+	open: usize,
+	// Identifies the placeholders reserved by this encoder, or zero if none
+	// have been reserved yet.
+	id: usize,
+}
+
+impl<'a> Encoder<'a> {
+	/// Creates a new `Encoder` that writes to the start of `buf`.
+	#[inline]
+	#[must_use]
+	pub fn new(buf: &'a mut [u8]) -> Encoder<'a> {
+		Encoder {
+			buf,
+			pos: 0,
+			reserved: 0,
+			open: 0,
+			id: 0,
+		}
+	}
+
+	/// Returns the number of bytes written so far.
+	#[inline]
+	#[must_use]
+	pub fn position(&self) -> usize {
+		self.pos
+	}
+
+	/// Returns the number of bytes remaining in the buffer.
+	#[inline]
+	#[must_use]
+	pub fn remaining(&self) -> usize {
+		self.buf.len() - self.pos
+	}
+
+	/// Consumes the `Encoder`, returning the written portion of the buffer.
+	#[inline]
+	#[must_use]
+	pub fn finish(self) -> &'a mut [u8] {
+		&mut self.buf[..self.pos]
+	}
+
+	/// Writes raw bytes to the buffer.
+	///
+	/// An error is returned if the remaining space is too small, in which case
+	/// the position is not advanced.
+	pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+		let available = self.remaining();
+		if bytes.len() > available {
+			return Err(EncodeError::BufferFull {
+				needed: bytes.len(),
+				available,
+			});
+		}
+		let start = self.pos;
+		self.pos += bytes.len();
+		self.buf[start..self.pos].copy_from_slice(bytes);
+		Ok(())
+	}
+
+	/// Reserves space for a `u64` that is not yet known.
+	///
+	/// The reserved slot is large enough to hold any value up to and
+	/// including `max`. Until it is filled, the slot contains an encoded zero.
+	///
+	/// An error is returned if the remaining space is too small, in which case
+	/// the position is not advanced.
+	pub fn reserve_u64(
+		&mut self,
+		max: u64,
+	) -> Result<Placeholder, EncodeError> {
+		let len = encoded_len_u64(max);
+		let available = self.remaining();
+		if len > available {
+			return Err(EncodeError::BufferFull {
+				needed: len,
+				available,
+			});
+		}
+		let offset = self.pos;
+		self.pos += len;
+		encode_padded(&mut self.buf[offset..self.pos], 0, len)?;
+		if self.id == 0 {
+			self.id = next_encoder_id(self);
+		}
+		self.reserved += 1;
+		let placeholder = Placeholder {
+			encoder_id: self.id,
+			seq: self.reserved,
+			prev_open: self.open,
+			offset,
+			len,
+			filled: false,
+		};
+		self.open = self.reserved;
+		Ok(placeholder)
+	}
+
+	/// Encodes a value of any supported type into the buffer.
+	///
+	/// An error is returned if the remaining space is too small, in which case
+	/// the position is not advanced.
+	#[inline]
+	pub fn write_vu128<T: Vu128>(
+		&mut self,
+		value: T,
+	) -> Result<(), EncodeError> {
+		let rest = &mut self.buf[self.pos..];
+		if rest.len() >= T::MAX_LEN {
+			// SAFETY: `rest` is valid for writes of `T::MAX_LEN` bytes, and
+			// is not used until the call returns.
+			self.pos += unsafe { T::encode_to_ptr(rest.as_mut_ptr(), value) };
+			return Ok(());
+		}
+		let mut tmp = T::Buf::default();
+		let len = T::encode(&mut tmp, value);
+		self.write_bytes(&tmp.as_ref()[..len])
+	}
+
+	/// Encodes a `u8` into the buffer.
+	///
+	/// An error is returned if the remaining space is too small, in which case
+	/// the position is not advanced.
+	#[inline]
+	pub fn write_u8(&mut self, value: u8) -> Result<(), EncodeError> {
+		self.write_vu128(value)
+	}
+
+	/// Encodes a `u16` into the buffer.
+	///
+	/// An error is returned if the remaining space is too small, in which case
+	/// the position is not advanced.
+	#[inline]
+	pub fn write_u16(&mut self, value: u16) -> Result<(), EncodeError> {
+		self.write_vu128(value)
+	}
+
+	/// Encodes a `u32` into the buffer.
+	///
+	/// An error is returned if the remaining space is too small, in which case
+	/// the position is not advanced.
+	#[inline]
+	pub fn write_u32(&mut self, value: u32) -> Result<(), EncodeError> {
+		self.write_vu128(value)
+	}
+
+	/// Encodes a `u64` into the buffer.
+	///
+	/// An error is returned if the remaining space is too small, in which case
+	/// the position is not advanced.
+	#[inline]
+	pub fn write_u64(&mut self, value: u64) -> Result<(), EncodeError> {
+		self.write_vu128(value)
+	}
+
+	/// Encodes a `u128` into the buffer.
+	///
+	/// An error is returned if the remaining space is too small, in which case
+	/// the position is not advanced.
+	#[inline]
+	pub fn write_u128(&mut self, value: u128) -> Result<(), EncodeError> {
+		self.write_vu128(value)
+	}
+
+	/// Encodes a `usize` into the buffer.
+	///
+	/// An error is returned if the remaining space is too small, in which case
+	/// the position is not advanced.
+	#[inline]
+	pub fn write_usize(&mut self, value: usize) -> Result<(), EncodeError> {
+		self.write_vu128(value)
+	}
+
+	/// Encodes an `i8` into the buffer.
+	///
+	/// An error is returned if the remaining space is too small, in which case
+	/// the position is not advanced.
+	#[inline]
+	pub fn write_i8(&mut self, value: i8) -> Result<(), EncodeError> {
+		self.write_vu128(value)
+	}
+
+	/// Encodes an `i16` into the buffer.
+	///
+	/// An error is returned if the remaining space is too small, in which case
+	/// the position is not advanced.
+	#[inline]
+	pub fn write_i16(&mut self, value: i16) -> Result<(), EncodeError> {
+		self.write_vu128(value)
+	}
+
+	/// Encodes an `i32` into the buffer.
+	///
+	/// An error is returned if the remaining space is too small, in which case
+	/// the position is not advanced.
+	#[inline]
+	pub fn write_i32(&mut self, value: i32) -> Result<(), EncodeError> {
+		self.write_vu128(value)
+	}
+
+	/// Encodes an `i64` into the buffer.
+	///
+	/// An error is returned if the remaining space is too small, in which case
+	/// the position is not advanced.
+	#[inline]
+	pub fn write_i64(&mut self, value: i64) -> Result<(), EncodeError> {
+		self.write_vu128(value)
+	}
+
+	/// Encodes an `i128` into the buffer.
+	///
+	/// An error is returned if the remaining space is too small, in which case
+	/// the position is not advanced.
+	#[inline]
+	pub fn write_i128(&mut self, value: i128) -> Result<(), EncodeError> {
+		self.write_vu128(value)
+	}
+
+	/// Encodes an `isize` into the buffer.
+	///
+	/// An error is returned if the remaining space is too small, in which case
+	/// the position is not advanced.
+	#[inline]
+	pub fn write_isize(&mut self, value: isize) -> Result<(), EncodeError> {
+		self.write_vu128(value)
+	}
+
+	/// Encodes an `f32` into the buffer.
+	///
+	/// An error is returned if the remaining space is too small, in which case
+	/// the position is not advanced.
+	#[inline]
+	pub fn write_f32(&mut self, value: f32) -> Result<(), EncodeError> {
+		self.write_vu128(value)
+	}
+
+	/// Encodes an `f64` into the buffer.
+	///
+	/// An error is returned if the remaining space is too small, in which case
+	/// the position is not advanced.
+	#[inline]
+	pub fn write_f64(&mut self, value: f64) -> Result<(), EncodeError> {
+		self.write_vu128(value)
+	}
+}
+
+/// Space for a `u64` reserved by [`Encoder::reserve_u64`].
+///
+/// A placeholder can only be filled by the encoder that reserved it, and only
+/// once. If filling fails, nothing is changed and the placeholder can be
+/// filled again.
+#[derive(Debug, Eq, PartialEq)]
+#[must_use]
+pub struct Placeholder {
+	encoder_id: usize,
+	seq: usize,
+	prev_open: usize,
+	offset: usize,
+	len: usize,
+	filled: bool,
+}
+
+impl Placeholder {
+	/// Returns the offset of the reserved slot within the encoder's buffer.
+	#[inline]
+	#[must_use]
+	pub fn offset(&self) -> usize {
+		self.offset
+	}
+
+	/// Returns the length of the reserved slot.
+	#[inline]
+	#[must_use]
+	pub fn encoded_len(&self) -> usize {
+		self.len
+	}
+
+	/// Writes `value` into the reserved slot.
+	///
+	/// The value is encoded using the full length of the slot, which may be
+	/// an over-long encoding. Over-long encodings are accepted by the
+	/// `decode_*` functions, but not by the `decode_*_canonical` functions.
+	///
+	/// An error is returned if the placeholder was not reserved by `encoder`
+	/// or has already been filled, or if the value is too large for the slot.
+	/// Nothing is written if an error is returned.
+	pub fn fill(
+		&mut self,
+		encoder: &mut Encoder,
+		value: u64,
+	) -> Result<(), EncodeError> {
+		self.check(encoder)?;
+		let slot = &mut encoder.buf[self.offset..self.offset + self.len];
+		encode_padded(slot, value.into(), self.len)?;
+		self.close(encoder);
+		Ok(())
+	}
+
+	/// Writes `value` into the reserved slot using its canonical encoding.
+	///
+	/// If the canonical encoding is shorter than the slot, the data written
+	/// after the slot is moved down to fill the gap. Any placeholders reserved
+	/// after this one must already have been filled, most recent first.
+	///
+	/// An error is returned if the placeholder was not reserved by `encoder`
+	/// or has already been filled, if a placeholder reserved after it has not
+	/// been filled, or if the value is too large for the slot. Nothing is
+	/// written if an error is returned.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut buf = [0u8; 32];
+	/// let mut encoder = vu128::Encoder::new(&mut buf);
+	///
+	/// let mut placeholder = encoder.reserve_u64(u64::from(u16::MAX))?;
+	/// encoder.write_bytes(b"hello")?;
+	/// placeholder.fill_compact(&mut encoder, 5)?;
+	///
+	/// assert_eq!(encoder.finish(), &[0x05, b'h', b'e', b'l', b'l', b'o']);
+	/// # Ok::<(), vu128::EncodeError>(())
+	/// ```
+	pub fn fill_compact(
+		&mut self,
+		encoder: &mut Encoder,
+		value: u64,
+	) -> Result<(), EncodeError> {
+		if !self.check(encoder)? {
+			return Err(EncodeError::InvalidPlaceholder);
+		}
+		let mut tmp = [0u8; MAX_LEN_U64];
+		let len = encode_u64(&mut tmp, value);
+		if len > self.len {
+			return Err(EncodeError::ValueTooLarge {
+				needed: len,
+				len: self.len,
+			});
+		}
+
+		let slot_end = self.offset + self.len;
+		let shift = self.len - len;
+		encoder.buf.copy_within(slot_end..encoder.pos, slot_end - shift);
+		encoder.pos -= shift;
+		encoder.buf[self.offset..self.offset + len]
+			.copy_from_slice(&tmp[..len]);
+		self.close(encoder);
+		Ok(())
+	}
+
+	// Checks that the placeholder was reserved by `encoder` and has not been
+	// filled, and returns whether it is the innermost unfilled placeholder.
+	//
+	// Compaction is only permitted for the innermost placeholder, so the slot
+	// of any other unfilled placeholder is never moved.
+	fn check(&self, encoder: &Encoder) -> Result<bool, EncodeError> {
+		if self.filled
+			|| self.encoder_id != encoder.id
+			|| self.seq > encoder.reserved
+			|| self.offset + self.len > encoder.pos
+		{
+			return Err(EncodeError::InvalidPlaceholder);
+		}
+		Ok(self.seq == encoder.open)
+	}
+
+	// Marks the placeholder as filled, after its slot has been written.
+	fn close(&mut self, encoder: &mut Encoder) {
+		if self.seq == encoder.open {
+			encoder.open = self.prev_open;
+		}
+		self.filled = true;
+	}
+}
+
+// Returns a new non-zero identifier for an encoder that reserves placeholders.
+// Identifiers are not reused until the counter wraps around.
+#[cfg(target_has_atomic = "ptr")]
+fn next_encoder_id(_encoder: &Encoder) -> usize {
+	use core::sync::atomic::{AtomicUsize, Ordering};
+
+	static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+	loop {
+		let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+		if id != 0 {
+			return id;
+		}
+	}
+}
+
+// Without atomic read-modify-write operations, fall back to the address of
+// the encoder's buffer. This is unique among encoders that exist at the same
+// time, but may be shared by a later encoder over the same buffer.
+#[cfg(not(target_has_atomic = "ptr"))]
+fn next_encoder_id(encoder: &Encoder) -> usize {
+	encoder.buf.as_ptr() as usize
+}
+
+/// Extends [`std::io::Write`] with methods for writing `vu128` values.
+///
+/// # Examples
+///
+/// ```
+/// use vu128::WriteVu128Ext;
+///
+/// let mut out = Vec::new();
+/// out.write_u32_vu128(12345)?;
+/// out.write_i64_vu128(-1)?;
+/// assert_eq!(out, &[0xB9, 0xC0, 0x01]);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg(feature = "std")]
+pub trait WriteVu128Ext: io::Write {
+	/// Writes a value of any supported type as a `vu128` value.
+	#[inline]
+	fn write_vu128<T: Vu128>(&mut self, value: T) -> io::Result<()> {
+		let mut buf = T::Buf::default();
+		let len = T::encode(&mut buf, value);
+		self.write_all(&buf.as_ref()[..len])
+	}
+
+	/// Writes a `u8` as a `vu128` value.
+	#[inline]
+	fn write_u8_vu128(&mut self, value: u8) -> io::Result<()> {
+		self.write_vu128(value)
+	}
+
+	/// Writes a `u16` as a `vu128` value.
+	#[inline]
+	fn write_u16_vu128(&mut self, value: u16) -> io::Result<()> {
+		self.write_vu128(value)
+	}
+
+	/// Writes a `u32` as a `vu128` value.
+	#[inline]
+	fn write_u32_vu128(&mut self, value: u32) -> io::Result<()> {
+		self.write_vu128(value)
+	}
+
+	/// Writes a `u64` as a `vu128` value.
+	#[inline]
+	fn write_u64_vu128(&mut self, value: u64) -> io::Result<()> {
+		self.write_vu128(value)
+	}
+
+	/// Writes a `u128` as a `vu128` value.
+	#[inline]
+	fn write_u128_vu128(&mut self, value: u128) -> io::Result<()> {
+		self.write_vu128(value)
+	}
+
+	/// Writes a `usize` as a `vu128` value.
+	#[inline]
+	fn write_usize_vu128(&mut self, value: usize) -> io::Result<()> {
+		self.write_vu128(value)
+	}
+
+	/// Writes an `i8` as a `vu128` value.
+	#[inline]
+	fn write_i8_vu128(&mut self, value: i8) -> io::Result<()> {
+		self.write_vu128(value)
+	}
+
+	/// Writes an `i16` as a `vu128` value.
+	#[inline]
+	fn write_i16_vu128(&mut self, value: i16) -> io::Result<()> {
+		self.write_vu128(value)
+	}
+
+	/// Writes an `i32` as a `vu128` value.
+	#[inline]
+	fn write_i32_vu128(&mut self, value: i32) -> io::Result<()> {
+		self.write_vu128(value)
+	}
+
+	/// Writes an `i64` as a `vu128` value.
+	#[inline]
+	fn write_i64_vu128(&mut self, value: i64) -> io::Result<()> {
+		self.write_vu128(value)
+	}
+
+	/// Writes an `i128` as a `vu128` value.
+	#[inline]
+	fn write_i128_vu128(&mut self, value: i128) -> io::Result<()> {
+		self.write_vu128(value)
+	}
+
+	/// Writes an `isize` as a `vu128` value.
+	#[inline]
+	fn write_isize_vu128(&mut self, value: isize) -> io::Result<()> {
+		self.write_vu128(value)
+	}
+
+	/// Writes an `f32` as a `vu128` value.
+	#[inline]
+	fn write_f32_vu128(&mut self, value: f32) -> io::Result<()> {
+		self.write_vu128(value)
+	}
+
+	/// Writes an `f64` as a `vu128` value.
+	#[inline]
+	fn write_f64_vu128(&mut self, value: f64) -> io::Result<()> {
+		self.write_vu128(value)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write + ?Sized> WriteVu128Ext for W {}
+
+/// Extends [`std::io::Read`] with methods for reading `vu128` values.
+///
+/// Each value is read with [`read_exact`](io::Read::read_exact), so partial
+/// reads are retried and no bytes beyond the end of the value are consumed.
+/// If the input ends before a complete value has been read, an error of kind
+/// [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) is returned. Values that
+/// cannot be decoded as the requested type are reported as an error of kind
+/// [`InvalidData`](io::ErrorKind::InvalidData) wrapping a [`DecodeError`].
+///
+/// # Examples
+///
+/// ```
+/// use vu128::ReadVu128Ext;
+///
+/// let mut input: &[u8] = &[0xB9, 0xC0, 0x01];
+/// assert_eq!(input.read_u32_vu128()?, 12345);
+/// assert_eq!(input.read_i64_vu128()?, -1);
+///
+/// let err = input.read_u32_vu128().unwrap_err();
+/// assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg(feature = "std")]
+pub trait ReadVu128Ext: io::Read {
+	/// Reads a value of any supported type encoded as a `vu128` value.
+	#[inline]
+	fn read_vu128<T: Vu128>(&mut self) -> io::Result<T> {
+		let mut buf = [0u8; MAX_LEN_U128];
+		self.read_exact(&mut buf[..1])?;
+		let len = encoded_len(buf[0]);
+		self.read_exact(&mut buf[1..len])?;
+		io_decoded(T::decode_from_slice(&buf[..len]))
+	}
+
+	/// Reads a `u8` encoded as a `vu128` value.
+	#[inline]
+	fn read_u8_vu128(&mut self) -> io::Result<u8> {
+		self.read_vu128()
+	}
+
+	/// Reads a `u16` encoded as a `vu128` value.
+	#[inline]
+	fn read_u16_vu128(&mut self) -> io::Result<u16> {
+		self.read_vu128()
+	}
+
+	/// Reads a `u32` encoded as a `vu128` value.
+	#[inline]
+	fn read_u32_vu128(&mut self) -> io::Result<u32> {
+		self.read_vu128()
+	}
+
+	/// Reads a `u64` encoded as a `vu128` value.
+	#[inline]
+	fn read_u64_vu128(&mut self) -> io::Result<u64> {
+		self.read_vu128()
+	}
+
+	/// Reads a `u128` encoded as a `vu128` value.
+	#[inline]
+	fn read_u128_vu128(&mut self) -> io::Result<u128> {
+		self.read_vu128()
+	}
+
+	/// Reads a `usize` encoded as a `vu128` value.
+	#[inline]
+	fn read_usize_vu128(&mut self) -> io::Result<usize> {
+		self.read_vu128()
+	}
+
+	/// Reads an `i8` encoded as a `vu128` value.
+	#[inline]
+	fn read_i8_vu128(&mut self) -> io::Result<i8> {
+		self.read_vu128()
+	}
+
+	/// Reads an `i16` encoded as a `vu128` value.
+	#[inline]
+	fn read_i16_vu128(&mut self) -> io::Result<i16> {
+		self.read_vu128()
+	}
+
+	/// Reads an `i32` encoded as a `vu128` value.
+	#[inline]
+	fn read_i32_vu128(&mut self) -> io::Result<i32> {
+		self.read_vu128()
+	}
+
+	/// Reads an `i64` encoded as a `vu128` value.
+	#[inline]
+	fn read_i64_vu128(&mut self) -> io::Result<i64> {
+		self.read_vu128()
+	}
+
+	/// Reads an `i128` encoded as a `vu128` value.
+	#[inline]
+	fn read_i128_vu128(&mut self) -> io::Result<i128> {
+		self.read_vu128()
+	}
+
+	/// Reads an `isize` encoded as a `vu128` value.
+	#[inline]
+	fn read_isize_vu128(&mut self) -> io::Result<isize> {
+		self.read_vu128()
+	}
+
+	/// Reads an `f32` encoded as a `vu128` value.
+	#[inline]
+	fn read_f32_vu128(&mut self) -> io::Result<f32> {
+		self.read_vu128()
+	}
+
+	/// Reads an `f64` encoded as a `vu128` value.
+	#[inline]
+	fn read_f64_vu128(&mut self) -> io::Result<f64> {
+		self.read_vu128()
+	}
+}
+
+#[cfg(feature = "std")]
+impl<R: io::Read + ?Sized> ReadVu128Ext for R {}
+
+/// Extends [`std::io::BufRead`] with methods for reading `vu128` values.
+///
+/// Values are decoded directly from the reader's internal buffer when it
+/// contains the entire encoded value, which avoids the per-value overhead of
+/// [`ReadVu128Ext`]. Values that straddle the end of the internal buffer are
+/// assembled from successive calls to [`fill_buf`](io::BufRead::fill_buf).
+///
+/// Errors are reported in the same way as [`ReadVu128Ext`].
+///
+/// The methods have the same names as those of [`ReadVu128Ext`], so that
+/// switching to a buffered reader only requires changing an import. They are
+/// in a separate trait because [`ReadVu128Ext`] is implemented for every
+/// [`io::Read`] type, and its methods cannot be overridden for readers that
+/// also implement [`io::BufRead`]. If both traits are in scope, calls on a
+/// buffered reader are ambiguous and must name the trait, for example
+/// `BufReadVu128Ext::read_u32_vu128(&mut input)`.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::BufReader;
+/// use vu128::BufReadVu128Ext;
+///
+/// let mut input = BufReader::new(&[0xB9, 0xC0, 0x01][..]);
+/// assert_eq!(input.read_u32_vu128()?, 12345);
+/// assert_eq!(input.read_i64_vu128()?, -1);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg(feature = "std")]
+pub trait BufReadVu128Ext: io::BufRead {
+	/// Reads a value of any supported type encoded as a `vu128` value from
+	/// the buffer.
+	#[inline]
+	fn read_vu128<T: Vu128>(&mut self) -> io::Result<T> {
+		match self.fill_buf() {
+			Ok(available) => {
+				if let Some(&b) = available.first() {
+					let len = encoded_len(b);
+					if available.len() >= len {
+						let decoded = T::decode_from_slice(available);
+						self.consume(len);
+						return io_decoded(decoded);
+					}
+				}
+			},
+			Err(err) if err.kind() == io::ErrorKind::Interrupted => {},
+			Err(err) => return Err(err),
+		}
+
+		let mut buf = [0u8; MAX_LEN_U128];
+		let mut len = 1;
+		let mut filled = 0;
+		while filled < len {
+			let available = match self.fill_buf() {
+				Ok(available) => available,
+				Err(err) if err.kind() == io::ErrorKind::Interrupted => {
+					continue;
+				},
+				Err(err) => return Err(err),
+			};
+			if available.is_empty() {
+				return Err(io::ErrorKind::UnexpectedEof.into());
+			}
+			let count = available.len().min(len - filled);
+			buf[filled..filled + count].copy_from_slice(&available[..count]);
+			self.consume(count);
+			filled += count;
+			len = encoded_len(buf[0]);
+		}
+		io_decoded(T::decode_from_slice(&buf[..len]))
+	}
+
+	/// Reads a `u8` encoded as a `vu128` value from the buffer.
+	#[inline]
+	fn read_u8_vu128(&mut self) -> io::Result<u8> {
+		BufReadVu128Ext::read_vu128(self)
+	}
+
+	/// Reads a `u16` encoded as a `vu128` value from the buffer.
+	#[inline]
+	fn read_u16_vu128(&mut self) -> io::Result<u16> {
+		BufReadVu128Ext::read_vu128(self)
+	}
+
+	/// Reads a `u32` encoded as a `vu128` value from the buffer.
+	#[inline]
+	fn read_u32_vu128(&mut self) -> io::Result<u32> {
+		BufReadVu128Ext::read_vu128(self)
+	}
+
+	/// Reads a `u64` encoded as a `vu128` value from the buffer.
+	#[inline]
+	fn read_u64_vu128(&mut self) -> io::Result<u64> {
+		BufReadVu128Ext::read_vu128(self)
+	}
+
+	/// Reads a `u128` encoded as a `vu128` value from the buffer.
+	#[inline]
+	fn read_u128_vu128(&mut self) -> io::Result<u128> {
+		BufReadVu128Ext::read_vu128(self)
+	}
+
+	/// Reads a `usize` encoded as a `vu128` value from the buffer.
+	#[inline]
+	fn read_usize_vu128(&mut self) -> io::Result<usize> {
+		BufReadVu128Ext::read_vu128(self)
+	}
+
+	/// Reads an `i8` encoded as a `vu128` value from the buffer.
+	#[inline]
+	fn read_i8_vu128(&mut self) -> io::Result<i8> {
+		BufReadVu128Ext::read_vu128(self)
+	}
+
+	/// Reads an `i16` encoded as a `vu128` value from the buffer.
+	#[inline]
+	fn read_i16_vu128(&mut self) -> io::Result<i16> {
+		BufReadVu128Ext::read_vu128(self)
+	}
+
+	/// Reads an `i32` encoded as a `vu128` value from the buffer.
+	#[inline]
+	fn read_i32_vu128(&mut self) -> io::Result<i32> {
+		BufReadVu128Ext::read_vu128(self)
+	}
+
+	/// Reads an `i64` encoded as a `vu128` value from the buffer.
+	#[inline]
+	fn read_i64_vu128(&mut self) -> io::Result<i64> {
+		BufReadVu128Ext::read_vu128(self)
+	}
+
+	/// Reads an `i128` encoded as a `vu128` value from the buffer.
+	#[inline]
+	fn read_i128_vu128(&mut self) -> io::Result<i128> {
+		BufReadVu128Ext::read_vu128(self)
+	}
+
+	/// Reads an `isize` encoded as a `vu128` value from the buffer.
+	#[inline]
+	fn read_isize_vu128(&mut self) -> io::Result<isize> {
+		BufReadVu128Ext::read_vu128(self)
+	}
+
+	/// Reads an `f32` encoded as a `vu128` value from the buffer.
+	#[inline]
+	fn read_f32_vu128(&mut self) -> io::Result<f32> {
+		BufReadVu128Ext::read_vu128(self)
+	}
+
+	/// Reads an `f64` encoded as a `vu128` value from the buffer.
+	#[inline]
+	fn read_f64_vu128(&mut self) -> io::Result<f64> {
+		BufReadVu128Ext::read_vu128(self)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<R: io::BufRead + ?Sized> BufReadVu128Ext for R {}
+
+/// Extends [`Vec<u8>`] with methods for appending `vu128` values.
+///
+/// Values are encoded directly into the vector's storage, without an
+/// intermediate buffer.
+///
+/// # Examples
+///
+/// ```
+/// use vu128::VecVu128Ext;
+///
+/// let mut out = Vec::new();
+/// out.push_u32_vu128(12345);
+/// out.push_i64_vu128(-1);
+/// assert_eq!(out, &[0xB9, 0xC0, 0x01]);
+/// ```
+#[cfg(feature = "alloc")]
+pub trait VecVu128Ext {
+	/// Appends a value of any supported type as a `vu128` value, returning
+	/// the encoded length.
+	fn push_vu128<T: Vu128>(&mut self, value: T) -> usize;
+
+	/// Appends a `u8` as a `vu128` value, returning the encoded length.
+	#[inline]
+	fn push_u8_vu128(&mut self, value: u8) -> usize {
+		self.push_vu128(value)
+	}
+
+	/// Appends a `u16` as a `vu128` value, returning the encoded length.
+	#[inline]
+	fn push_u16_vu128(&mut self, value: u16) -> usize {
+		self.push_vu128(value)
+	}
+
+	/// Appends a `u32` as a `vu128` value, returning the encoded length.
+	#[inline]
+	fn push_u32_vu128(&mut self, value: u32) -> usize {
+		self.push_vu128(value)
+	}
+
+	/// Appends a `u64` as a `vu128` value, returning the encoded length.
+	#[inline]
+	fn push_u64_vu128(&mut self, value: u64) -> usize {
+		self.push_vu128(value)
+	}
+
+	/// Appends a `u128` as a `vu128` value, returning the encoded length.
+	#[inline]
+	fn push_u128_vu128(&mut self, value: u128) -> usize {
+		self.push_vu128(value)
+	}
+
+	/// Appends a `usize` as a `vu128` value, returning the encoded length.
+	#[inline]
+	fn push_usize_vu128(&mut self, value: usize) -> usize {
+		self.push_vu128(value)
+	}
+
+	/// Appends an `i8` as a `vu128` value, returning the encoded length.
+	#[inline]
+	fn push_i8_vu128(&mut self, value: i8) -> usize {
+		self.push_vu128(value)
+	}
+
+	/// Appends an `i16` as a `vu128` value, returning the encoded length.
+	#[inline]
+	fn push_i16_vu128(&mut self, value: i16) -> usize {
+		self.push_vu128(value)
+	}
+
+	/// Appends an `i32` as a `vu128` value, returning the encoded length.
+	#[inline]
+	fn push_i32_vu128(&mut self, value: i32) -> usize {
+		self.push_vu128(value)
+	}
+
+	/// Appends an `i64` as a `vu128` value, returning the encoded length.
+	#[inline]
+	fn push_i64_vu128(&mut self, value: i64) -> usize {
+		self.push_vu128(value)
+	}
+
+	/// Appends an `i128` as a `vu128` value, returning the encoded length.
+	#[inline]
+	fn push_i128_vu128(&mut self, value: i128) -> usize {
+		self.push_vu128(value)
+	}
+
+	/// Appends an `isize` as a `vu128` value, returning the encoded length.
+	#[inline]
+	fn push_isize_vu128(&mut self, value: isize) -> usize {
+		self.push_vu128(value)
+	}
+
+	/// Appends an `f32` as a `vu128` value, returning the encoded length.
+	#[inline]
+	fn push_f32_vu128(&mut self, value: f32) -> usize {
+		self.push_vu128(value)
+	}
+
+	/// Appends an `f64` as a `vu128` value, returning the encoded length.
+	#[inline]
+	fn push_f64_vu128(&mut self, value: f64) -> usize {
+		self.push_vu128(value)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl VecVu128Ext for Vec<u8> {
+	#[inline]
+	fn push_vu128<T: Vu128>(&mut self, value: T) -> usize {
+		self.reserve(T::MAX_LEN);
+		let old_len = self.len();
+		// SAFETY: `reserve()` ensures the allocation extends at least
+		// `T::MAX_LEN` bytes past `old_len`.
+		let spare = unsafe { self.as_mut_ptr().add(old_len) };
+		// SAFETY: `spare` is valid for writes of `T::MAX_LEN` bytes, and the
+		// spare capacity is not accessed through any other pointer.
+		let len = unsafe { T::encode_to_ptr(spare, value) };
+		// SAFETY: The first `len` bytes of spare capacity were initialized by
+		// the encoder.
+		unsafe { self.set_len(old_len + len) };
+		len
+	}
+}
+
+/// An error encountered while encoding a `vu128` value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum EncodeError {
+	/// The value's encoding is longer than the requested length.
+	ValueTooLarge {
+		/// The number of bytes required to encode the value.
+		needed: usize,
+		/// The requested encoded length.
+		len: usize,
+	},
+
+	/// The output buffer is too small to hold the encoded value.
+	BufferFull {
+		/// The number of bytes required to write the value.
+		needed: usize,
+		/// The number of bytes available in the buffer.
+		available: usize,
+	},
+
+	/// The requested encoded length is zero, or is greater than the maximum
+	/// encoded length of the value's type.
+	InvalidLength {
+		/// The requested encoded length.
+		len: usize,
+		/// The maximum encoded length of the value's type.
+		max_len: usize,
+	},
+
+	/// The placeholder was not reserved by this encoder, has already been
+	/// filled, or was compacted before a placeholder reserved after it was
+	/// filled.
+	InvalidPlaceholder,
+}
+
+impl fmt::Display for EncodeError {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			EncodeError::ValueTooLarge { needed, len } => write!(
+				fmt,
+				"value too large for encoded length {} (needed {} bytes)",
+				len, needed,
+			),
+			EncodeError::BufferFull { needed, available } => write!(
+				fmt,
+				"buffer full (needed {} bytes, {} available)",
+				needed, available,
+			),
+			EncodeError::InvalidLength { len, max_len } => write!(
+				fmt,
+				"invalid encoded length {} (maximum {})",
+				len, max_len,
+			),
+			EncodeError::InvalidPlaceholder => write!(
+				fmt,
+				"placeholder not reserved by this encoder, already filled, or filled out of order",
+			),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EncodeError {}
+
+/// An error encountered while decoding a `vu128` value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DecodeError {
+	kind: DecodeErrorKind,
+	offset: usize,
+}
+
+impl DecodeError {
+	#[inline]
+	const fn new(kind: DecodeErrorKind) -> DecodeError {
+		DecodeError { kind, offset: 0 }
+	}
+
+	#[inline]
+	const fn offset_by(self, offset: usize) -> DecodeError {
+		DecodeError {
+			kind: self.kind,
+			offset: self.offset + offset,
+		}
+	}
+
+	/// Returns the kind of error.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use vu128::DecodeErrorKind;
+	/// let err = vu128::decode_u32_from_slice(&[]).unwrap_err();
+	/// let expect = DecodeErrorKind::Truncated { needed: 1, available: 0 };
+	/// assert_eq!(err.kind(), expect);
+	/// ```
+	#[inline]
+	#[must_use]
+	pub const fn kind(&self) -> DecodeErrorKind {
+		self.kind
+	}
+
+	/// Returns the offset of the value that could not be decoded, relative
+	/// to the start of the input.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let err = vu128::decode_u32_from_slice(&[]).unwrap_err();
+	/// assert_eq!(err.offset(), 0);
+	/// ```
+	#[inline]
+	#[must_use]
+	pub const fn offset(&self) -> usize {
+		self.offset
+	}
+}
+
+impl fmt::Display for DecodeError {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		write!(fmt, "{} at offset {}", self.kind, self.offset)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+/// An error encountered while decoding `vu128` values into a slice.
+///
+/// The values decoded before the error are written to the start of the
+/// output slice, and [`DecodeSliceError::produced`] returns their count.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DecodeSliceError {
+	error: DecodeError,
+	produced: usize,
+}
+
+impl DecodeSliceError {
+	/// Returns the error for the value that could not be decoded.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use vu128::DecodeErrorKind;
+	/// let mut out = [0; 4];
+	/// let input = [0x01, 0x02, 0xF4, 0x00, 0x00, 0x00, 0x00, 0x01];
+	/// let err = vu128::decode_u32_slice(&input, &mut out).unwrap_err();
+	/// let expect = DecodeErrorKind::Overflow { encoded_len: 6, max_len: 5 };
+	/// assert_eq!(err.error().kind(), expect);
+	/// assert_eq!(err.error().offset(), 2);
+	/// ```
+	#[inline]
+	#[must_use]
+	pub const fn error(&self) -> DecodeError {
+		self.error
+	}
+
+	/// Returns the number of values decoded before the error.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut out = [0; 4];
+	/// let input = [0x01, 0x02, 0xF4, 0x00, 0x00, 0x00, 0x00, 0x01];
+	/// let err = vu128::decode_u32_slice(&input, &mut out).unwrap_err();
+	/// assert_eq!(err.produced(), 2);
+	/// assert_eq!(&out[..err.produced()], &[1, 2]);
+	/// ```
+	#[inline]
+	#[must_use]
+	pub const fn produced(&self) -> usize {
+		self.produced
+	}
+}
+
+impl From<DecodeSliceError> for DecodeError {
+	#[inline]
+	fn from(err: DecodeSliceError) -> DecodeError {
+		err.error
+	}
+}
+
+impl fmt::Display for DecodeSliceError {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		write!(fmt, "{} (after {} values)", self.error, self.produced)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeSliceError {}
+
+/// The kind of a [`DecodeError`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum DecodeErrorKind {
+	/// The input is shorter than the encoded length indicated by its first
+	/// byte.
+	Truncated {
+		/// The number of bytes required to decode the value.
+		needed: usize,
+		/// The number of bytes available in the input.
+		available: usize,
+	},
+
+	/// The encoded value does not fit in the target type.
+	Overflow {
+		/// The encoded length indicated by the first byte.
+		encoded_len: usize,
+		/// The maximum encoded length of the target type.
+		max_len: usize,
+	},
+
+	/// The encoded length fits the target type, but the decoded value is
+	/// outside of its range.
+	OutOfRange,
+
+	/// The input is an over-long encoding, and the decoder requires that
+	/// each value have a single canonical encoding.
+	NonCanonical(NonCanonicalReason),
+
+	/// The encoded value is not a Unicode scalar value.
+	InvalidChar {
+		/// The decoded value.
+		value: u32,
+	},
+}
+
+impl fmt::Display for DecodeErrorKind {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			DecodeErrorKind::Truncated { needed, available } => write!(
+				fmt,
+				"truncated input (needed {} bytes, {} available)",
+				needed, available,
+			),
+			DecodeErrorKind::Overflow {
+				encoded_len,
+				max_len,
+			} => write!(
+				fmt,
+				"value overflows target type (encoded length {}, maximum {})",
+				encoded_len, max_len,
+			),
+			DecodeErrorKind::OutOfRange => {
+				write!(fmt, "value out of range for target type")
+			},
+			DecodeErrorKind::NonCanonical(reason) => {
+				write!(fmt, "non-canonical encoding ({})", reason)
+			},
+			DecodeErrorKind::InvalidChar { value } => {
+				write!(fmt, "invalid Unicode scalar value 0x{:X}", value)
+			},
+		}
+	}
+}
+
+/// The reason an encoding was rejected as non-canonical.
+///
+/// See the [module documentation](crate#handling-of-over-long-encodings)
+/// for details on over-long encodings.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum NonCanonicalReason {
+	/// The payload has zero-padding beyond that required to reach a multiple
+	/// of 7 or 8 bits.
+	ZeroPadding,
+
+	/// A unary length prefix byte was used for a value in the range
+	/// `[0, 2^7)`.
+	LengthPrefix,
+
+	/// A binary length prefix byte was used for a value in the range
+	/// `[0, 2^28)`.
+	BinaryLengthPrefix,
+}
+
+impl fmt::Display for NonCanonicalReason {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.write_str(match self {
+			NonCanonicalReason::ZeroPadding => "unnecessary zero padding",
+			NonCanonicalReason::LengthPrefix => {
+				"length prefix for value less than 2^7"
+			},
+			NonCanonicalReason::BinaryLengthPrefix => {
+				"binary length prefix for value less than 2^28"
+			},
+		})
+	}
+}
+
+#[inline(always)]
+fn check_encoded_len(b: u8, max_len: usize) -> Result<(), DecodeError> {
+	let len = encoded_len(b);
+	if len > max_len {
+		return Err(DecodeError::new(DecodeErrorKind::Overflow {
+			encoded_len: len,
+			max_len,
+		}));
+	}
+	Ok(())
+}
+
+// Decodes a `u32` as `decode_u32()` does, but ignoring any bytes of `buf`
+// beyond the encoded length. These may belong to the next value when `buf`
+// is a window into a larger input.
+#[inline(always)]
+fn decode_u32_exact(buf: &BufU32) -> (u32, usize) {
+	let (value, len) = decode_u32(buf);
+	if buf[0] < 0xF0 || len >= MAX_LEN_U32 {
+		return (value, len);
+	}
+	let mask = u32::MAX >> ((MAX_LEN_U32 - len) * 8);
+	(value & mask, len)
+}
+
+#[inline(always)]
+fn decode_i32_exact(buf: &BufU32) -> (i32, usize) {
+	let (zz, len) = decode_u32_exact(buf);
+	let value = ((zz >> 1) as i32) ^ (-((zz & 1) as i32));
+	(value, len)
+}
+
+#[inline(always)]
+fn widen_buf<const N: usize>(buf: &[u8; N]) -> BufU32 {
+	let mut wide = [0u8; MAX_LEN_U32];
+	wide[..N].copy_from_slice(buf);
+	wide
+}
+
+#[inline(always)]
+fn narrow_decoded<T: TryFrom<U>, U>(
+	value: U,
+	len: usize,
+) -> Result<(T, usize), DecodeError> {
+	match T::try_from(value) {
+		Ok(value) => Ok((value, len)),
+		Err(_) => Err(DecodeError::new(DecodeErrorKind::OutOfRange)),
+	}
+}
+
+#[inline(always)]
+fn nonzero_decoded<T>(
+	value: Option<T>,
+	len: usize,
+) -> Result<(T, usize), DecodeError> {
+	match value {
+		Some(value) => Ok((value, len)),
+		None => Err(DecodeError::new(DecodeErrorKind::OutOfRange)),
+	}
+}
+
+#[inline(always)]
+fn char_decoded(value: u32, len: usize) -> Result<(char, usize), DecodeError> {
+	match char::from_u32(value) {
+		Some(value) => Ok((value, len)),
+		None => Err(DecodeError::new(DecodeErrorKind::InvalidChar { value })),
+	}
+}
+
+#[inline(always)]
+fn encode_slice<T: Vu128>(values: &[T], out: &mut [u8]) -> usize {
+	// Only sum the exact encoded length if the worst case might not fit. The
+	// check is done before writing anything, so a panic leaves `out`
+	// unmodified.
+	let worst_case = values.len().saturating_mul(T::MAX_LEN);
+	if worst_case > out.len() {
+		let needed: usize = values.iter().map(|&v| T::encoded_len(v)).sum();
+		assert!(
+			needed <= out.len(),
+			"output buffer too small (needed {} bytes, {} available)",
+			needed,
+			out.len(),
+		);
+	}
+
+	// `out` is only accessed through `out_ptr` until the direct encoding
+	// loops below have finished, as required by `encode_to_ptr()`.
+	let out_len = out.len();
+	let out_ptr = out.as_mut_ptr();
+	let mut pos = 0;
+	let mut ii = 0;
+
+	// While there is room for four values of the maximum length, encode
+	// directly into the output without checking each value's length.
+	while ii + 4 <= values.len() && out_len - pos >= 4 * T::MAX_LEN {
+		let chunk = &values[ii..ii + 4];
+		// SAFETY: Each value is at most `T::MAX_LEN` bytes, and at least
+		// `4 * T::MAX_LEN` bytes of `out` remain after `pos`.
+		unsafe {
+			pos += T::encode_to_ptr(out_ptr.add(pos), chunk[0]);
+			pos += T::encode_to_ptr(out_ptr.add(pos), chunk[1]);
+			pos += T::encode_to_ptr(out_ptr.add(pos), chunk[2]);
+			pos += T::encode_to_ptr(out_ptr.add(pos), chunk[3]);
+		}
+		ii += 4;
+	}
+	while ii < values.len() && out_len - pos >= T::MAX_LEN {
+		// SAFETY: At least `T::MAX_LEN` bytes of `out` remain after `pos`.
+		pos += unsafe { T::encode_to_ptr(out_ptr.add(pos), values[ii]) };
+		ii += 1;
+	}
+
+	// Values near the end of `out` are encoded into a temporary buffer, so
+	// that only their actual length needs to fit.
+	for &value in &values[ii..] {
+		let mut tmp = T::Buf::default();
+		let len = T::encode(&mut tmp, value);
+		out[pos..pos + len].copy_from_slice(&tmp.as_ref()[..len]);
+		pos += len;
+	}
+	pos
+}
+
+// The `decode_fn` must decode every encoding of at most `N` bytes without
+// loss, which is true of the 32, 64, and 128 bit types.
+#[inline(always)]
+fn decode_slice<T: Vu128, const N: usize>(
+	input: &[u8],
+	out: &mut [T],
+	decode_fn: fn(&[u8; N]) -> (T, usize),
+) -> Result<(usize, usize), DecodeSliceError> {
+	let mut pos = 0;
+	let mut produced = 0;
+	for slot in out.iter_mut() {
+		let rest = &input[pos..];
+		let fixed = rest.get(..N).and_then(|b| <&[u8; N]>::try_from(b).ok());
+		let (value, len) = match fixed {
+			Some(fixed) if encoded_len(fixed[0]) <= N => decode_fn(fixed),
+			_ => {
+				match rest.first() {
+					Some(&b) if encoded_len(b) <= rest.len() => {},
+					_ => break,
+				}
+				match T::decode_from_slice(rest) {
+					Ok(decoded) => decoded,
+					Err(err) => {
+						return Err(DecodeSliceError {
+							error: err.offset_by(pos),
+							produced,
+						});
+					},
+				}
+			},
+		};
+		*slot = value;
+		produced += 1;
+		pos += len;
+	}
+	Ok((pos, produced))
+}
+
+// Bulk decoding of `u32` values with SSE4.1 or AVX2, selected at runtime.
+//
+// The SSE4.1 kernel decodes four values from a 16-byte window. The lengths of
+// the values are read from their prefix bytes and packed into an 8-bit key,
+// which selects a shuffle mask that moves the bytes of each value into its own
+// 32-bit lane. The prefix bits are then cleared and the payload bits joined
+// with a per-lane mask and multiply.
+//
+// The AVX2 kernel does the same for eight values at a time, with the second
+// group of four loaded into the upper 128-bit lane from where the first group
+// ends. Both kernels also have a fast path for runs of values in the range
+// `[0, 2^7)`, which are widened directly.
+//
+// Values with a binary length prefix are left to the scalar decoder.
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+mod x86 {
+	use core::arch::x86_64::*;
+
+	use super::{decode_slice, decode_u32_exact, DecodeSliceError};
+
+	/// A SIMD kernel for decoding `u32` values.
+	#[derive(Clone, Copy, Debug)]
+	pub enum Kernel {
+		/// Decodes four values per step with SSE4.1.
+		Sse41,
+		/// Decodes eight values per step with AVX2.
+		Avx2,
+	}
+
+	pub(crate) fn detect_kernel() -> Option<Kernel> {
+		if is_supported(Kernel::Avx2) {
+			return Some(Kernel::Avx2);
+		}
+		if is_supported(Kernel::Sse41) {
+			return Some(Kernel::Sse41);
+		}
+		None
+	}
+
+	pub(crate) fn is_supported(kernel: Kernel) -> bool {
+		match kernel {
+			Kernel::Sse41 => std::is_x86_feature_detected!("sse4.1"),
+			Kernel::Avx2 => std::is_x86_feature_detected!("avx2"),
+		}
+	}
+
+	/// Decodes values as `super::decode_u32_slice()` does, using `kernel`
+	/// for runs of values with unary length prefixes.
+	///
+	/// # Safety
+	///
+	/// The CPU must support the target features of `kernel`.
+	pub(crate) unsafe fn decode_u32_slice(
+		input: &[u8],
+		out: &mut [u32],
+		kernel: Kernel,
+	) -> Result<(usize, usize), DecodeSliceError> {
+		let mut pos = 0;
+		let mut produced = 0;
+		loop {
+			let (len, n) = match kernel {
+				Kernel::Sse41 => {
+					decode_sse41(&input[pos..], &mut out[produced..])
+				},
+				Kernel::Avx2 => {
+					decode_avx2(&input[pos..], &mut out[produced..])
+				},
+			};
+			pos += len;
+			produced += n;
+
+			// The kernel stops when fewer than 16 bytes of input or four
+			// values of output remain, in which case the rest are decoded
+			// by the scalar decoder. Otherwise one of the next four values
+			// has a binary length prefix, and those four are decoded by the
+			// scalar decoder before resuming.
+			let rest = &input[pos..];
+			let end = if rest.len() < 16 || out.len() - produced < 4 {
+				out.len()
+			} else {
+				produced + 4
+			};
+			let slots = &mut out[produced..end];
+			let (len, n) = match decode_slice(rest, slots, decode_u32_exact) {
+				Ok(decoded) => decoded,
+				Err(err) => {
+					return Err(DecodeSliceError {
+						error: err.error.offset_by(pos),
+						produced: produced + err.produced,
+					});
+				},
+			};
+			pos += len;
+			produced += n;
+			if produced < end || end == out.len() {
+				break;
+			}
+		}
+		Ok((pos, produced))
+	}
+
+	/// Decodes values from the start of `input` into `out`, returning the
+	/// number of bytes consumed and values produced.
+	///
+	/// # Safety
+	///
+	/// The CPU must support SSE4.1.
+	#[target_feature(enable = "sse4.1")]
+	unsafe fn decode_sse41(input: &[u8], out: &mut [u32]) -> (usize, usize) {
+		let mut pos = 0;
+		let mut produced = 0;
+		while let Some((len, n)) =
+			decode_step(&input[pos..], &mut out[produced..])
+		{
+			pos += len;
+			produced += n;
+		}
+		(pos, produced)
+	}
+
+	/// Decodes values from the start of `input` into `out`, returning the
+	/// number of bytes consumed and values produced.
+	///
+	/// # Safety
+	///
+	/// The CPU must support AVX2.
+	#[target_feature(enable = "avx2")]
+	unsafe fn decode_avx2(input: &[u8], out: &mut [u32]) -> (usize, usize) {
+		let mut pos = 0;
+		let mut produced = 0;
+		while let Some((len, n)) =
+			decode_step_avx2(&input[pos..], &mut out[produced..])
+				.or_else(|| decode_step(&input[pos..], &mut out[produced..]))
+		{
+			pos += len;
+			produced += n;
+		}
+		(pos, produced)
+	}
+
+	/// Decodes sixteen values if the next 16 bytes of `input` are all in the
+	/// range `[0, 2^7)`, or otherwise four values with unary length prefixes.
+	/// Returns `None` if fewer than 16 bytes of input or 4 values of output
+	/// remain, or if one of the four values has a binary length prefix.
+	///
+	/// # Safety
+	///
+	/// The CPU must support SSE4.1.
+	#[target_feature(enable = "sse4.1")]
+	#[inline]
+	unsafe fn decode_step(
+		input: &[u8],
+		out: &mut [u32],
+	) -> Option<(usize, usize)> {
+		if input.len() < 16 || out.len() < 4 {
+			return None;
+		}
+		let chunk = _mm_loadu_si128(input.as_ptr().cast());
+		let dst = out.as_mut_ptr().cast::<__m128i>();
+
+		if _mm_movemask_epi8(chunk) == 0 && out.len() >= 16 {
+			_mm_storeu_si128(dst, _mm_cvtepu8_epi32(chunk));
+			let chunk = _mm_srli_si128(chunk, 4);
+			_mm_storeu_si128(dst.add(1), _mm_cvtepu8_epi32(chunk));
+			let chunk = _mm_srli_si128(chunk, 4);
+			_mm_storeu_si128(dst.add(2), _mm_cvtepu8_epi32(chunk));
+			let chunk = _mm_srli_si128(chunk, 4);
+			_mm_storeu_si128(dst.add(3), _mm_cvtepu8_epi32(chunk));
+			return Some((16, 16));
+		}
+
+		let (key, len) = quad_key(input)?;
+		let entry = &QUAD_TABLE[key];
+		let shuffle = _mm_loadu_si128(entry.shuffle.as_ptr().cast());
+		let mask = _mm_loadu_si128(entry.mask.as_ptr().cast());
+		let scale = _mm_loadu_si128(entry.scale.as_ptr().cast());
+		let raw = _mm_shuffle_epi8(chunk, shuffle);
+		let low = _mm_and_si128(raw, mask);
+		let high = _mm_mullo_epi32(_mm_srli_epi32(raw, 8), scale);
+		_mm_storeu_si128(dst, _mm_or_si128(low, high));
+		Some((len, 4))
+	}
+
+	/// Decodes thirty-two values if the next 32 bytes of `input` are all in
+	/// the range `[0, 2^7)`, or otherwise eight values with unary length
+	/// prefixes. Returns `None` if fewer than 32 bytes of input or 8 values of
+	/// output remain, or if one of the eight values has a binary length prefix.
+	///
+	/// # Safety
+	///
+	/// The CPU must support AVX2.
+	#[target_feature(enable = "avx2")]
+	#[inline]
+	unsafe fn decode_step_avx2(
+		input: &[u8],
+		out: &mut [u32],
+	) -> Option<(usize, usize)> {
+		if input.len() < 32 || out.len() < 8 {
+			return None;
+		}
+		let src = input.as_ptr();
+		let dst = out.as_mut_ptr().cast::<__m256i>();
+
+		let chunk = _mm256_loadu_si256(src.cast());
+		if _mm256_movemask_epi8(chunk) == 0 && out.len() >= 32 {
+			let lo = _mm256_castsi256_si128(chunk);
+			let hi = _mm256_extracti128_si256(chunk, 1);
+			_mm256_storeu_si256(dst, _mm256_cvtepu8_epi32(lo));
+			let lo = _mm_srli_si128(lo, 8);
+			_mm256_storeu_si256(dst.add(1), _mm256_cvtepu8_epi32(lo));
+			_mm256_storeu_si256(dst.add(2), _mm256_cvtepu8_epi32(hi));
+			let hi = _mm_srli_si128(hi, 8);
+			_mm256_storeu_si256(dst.add(3), _mm256_cvtepu8_epi32(hi));
+			return Some((32, 32));
+		}
+
+		// The first four values are at most 16 bytes long, so the 16-byte
+		// window of the second four is within the 32 bytes of input.
+		let (key_lo, len_lo) = quad_key(input)?;
+		let (key_hi, len_hi) = quad_key(&input[len_lo..])?;
+		let lo = &QUAD_TABLE[key_lo];
+		let hi = &QUAD_TABLE[key_hi];
+		let chunk = _mm256_loadu2_m128i(src.add(len_lo).cast(), src.cast());
+		let shuffle = _mm256_loadu2_m128i(
+			hi.shuffle.as_ptr().cast(),
+			lo.shuffle.as_ptr().cast(),
+		);
+		let mask = _mm256_loadu2_m128i(
+			hi.mask.as_ptr().cast(),
+			lo.mask.as_ptr().cast(),
+		);
+		let scale = _mm256_loadu2_m128i(
+			hi.scale.as_ptr().cast(),
+			lo.scale.as_ptr().cast(),
+		);
+		let raw = _mm256_shuffle_epi8(chunk, shuffle);
+		let low = _mm256_and_si256(raw, mask);
+		let high = _mm256_mullo_epi32(_mm256_srli_epi32(raw, 8), scale);
+		_mm256_storeu_si256(dst, _mm256_or_si256(low, high));
+		Some((len_lo + len_hi, 8))
+	}
+
+	// Returns the `QUAD_TABLE` key and total length of the four values at the
+	// start of `input`, or `None` if one of them has a binary length prefix.
+	#[inline(always)]
+	fn quad_key(input: &[u8]) -> Option<(usize, usize)> {
+		let len0 = unary_len(input[0])?;
+		let len1 = unary_len(input[len0])?;
+		let len2 = unary_len(input[len0 + len1])?;
+		let len3 = unary_len(input[len0 + len1 + len2])?;
+		let key = (len0 - 1)
+			| ((len1 - 1) << 2)
+			| ((len2 - 1) << 4)
+			| ((len3 - 1) << 6);
+		Some((key, len0 + len1 + len2 + len3))
+	}
+
+	// Same as `encoded_len()` for unary length prefixes, but without
+	// branching on the length.
+	#[inline(always)]
+	fn unary_len(b: u8) -> Option<usize> {
+		let len = b.leading_ones() as usize + 1;
+		if len > 4 {
+			return None;
+		}
+		Some(len)
+	}
+
+	// For a value of length `len` with a unary prefix, the low `8 - len` bits
+	// of the first byte are the low bits of the value, and the remaining
+	// bytes are the high bits.
+	#[derive(Clone, Copy)]
+	struct QuadEntry {
+		shuffle: [u8; 16],
+		mask: [u32; 4],
+		scale: [u32; 4],
+	}
+
+	static QUAD_TABLE: [QuadEntry; 256] = quad_table();
+
+	const fn quad_table() -> [QuadEntry; 256] {
+		let mut table = [QuadEntry {
+			shuffle: [0; 16],
+			mask: [0; 4],
+			scale: [0; 4],
+		}; 256];
+		let mut key = 0;
+		while key < 256 {
+			let mut offset = 0;
+			let mut lane = 0;
+			while lane < 4 {
+				let len = ((key >> (lane * 2)) & 0b11) + 1;
+				let mut ii = 0;
+				while ii < 4 {
+					table[key].shuffle[lane * 4 + ii] =
+						if ii < len { (offset + ii) as u8 } else { 0x80 };
+					ii += 1;
+				}
+				table[key].mask[lane] = 0xFF >> len;
+				table[key].scale[lane] = 1 << (8 - len);
+				offset += len;
+				lane += 1;
+			}
+			key += 1;
+		}
+		table
+	}
+
+}
+
+#[cfg(feature = "std")]
+#[inline(always)]
+fn io_decoded<T>(decoded: Result<(T, usize), DecodeError>) -> io::Result<T> {
+	match decoded {
+		Ok((value, _)) => Ok(value),
+		Err(err) => Err(io::Error::new(io::ErrorKind::InvalidData, err)),
+	}
+}
+
+fn encode_padded(
+	buf: &mut [u8],
+	value: u128,
+	len: usize,
+) -> Result<(), EncodeError> {
+	let max_len = buf.len();
+	if len == 0 || len > max_len {
+		return Err(EncodeError::InvalidLength { len, max_len });
+	}
+	let needed = encoded_len_u128(value);
+	if needed > len {
+		return Err(EncodeError::ValueTooLarge { needed, len });
+	}
+
+	if len == 1 {
+		buf[0] = value as u8;
+		return Ok(());
+	}
+	if len <= 4 {
+		let x = (value as u32) << len;
+		buf[0] = !(0xFF >> (len - 1)) | ((x as u8) >> len);
+		for (ii, b) in buf[1..len].iter_mut().enumerate() {
+			*b = (x >> ((ii + 1) * 8)) as u8;
+		}
+		return Ok(());
+	}
+	buf[0] = 0xF0 | ((len - 2) as u8);
+	buf[1..len].copy_from_slice(&value.to_le_bytes()[..len - 1]);
+	Ok(())
+}
+
+fn check_canonical(encoded: &[u8]) -> Result<(), DecodeError> {
+	let len = encoded.len();
+	if len == 1 {
+		return Ok(());
+	}
+	let mut tmp = [0u8; MAX_LEN_U128];
+	tmp[..len].copy_from_slice(encoded);
+	let (value, _) = decode_u128(&tmp);
+
+	// A binary prefix for a small value is reported as such even when the
+	// value would also fit in a single byte.
+	let reason = if encoded[0] >= 0xF0 && value < 0x10000000 {
+		NonCanonicalReason::BinaryLengthPrefix
+	} else if value < 0x80 {
+		NonCanonicalReason::LengthPrefix
+	} else if len > encoded_len_u128(value) {
+		NonCanonicalReason::ZeroPadding
+	} else {
+		return Ok(());
+	};
+	Err(DecodeError::new(DecodeErrorKind::NonCanonical(reason)))
+}
+
+// Decodes an unsigned value of at most `max_len` encoded bytes from a slice.
+// Longer encodings are rejected even if their extra payload bytes are zero,
+// so that the result matches that of the `decode_*_checked` functions.
+#[inline(always)]
+fn decode_unsigned_from_slice<T, const N: usize>(
+	buf: &[u8],
+	max_len: usize,
+	decode_fn: fn(&[u8; N]) -> (T, usize),
+) -> Result<(T, usize), DecodeError> {
+	let len = match buf.first() {
+		Some(&b) => encoded_len(b),
+		None => {
+			return Err(DecodeError::new(DecodeErrorKind::Truncated {
+				needed: 1,
+				available: 0,
+			}));
+		},
+	};
+	if len > max_len {
+		return Err(DecodeError::new(DecodeErrorKind::Overflow {
+			encoded_len: len,
+			max_len,
+		}));
+	}
+	if buf.len() < len {
+		return Err(DecodeError::new(DecodeErrorKind::Truncated {
+			needed: len,
+			available: buf.len(),
+		}));
+	}
+
+	// Decode in place if the input is at least as long as the target type's
+	// buffer, otherwise copy into a zero-padded temporary.
+	let fixed = buf.get(..N).and_then(|b| <&[u8; N]>::try_from(b).ok());
+	if let Some(fixed) = fixed {
+		return Ok(decode_fn(fixed));
+	}
+	let mut tmp = [0u8; N];
+	tmp[..len].copy_from_slice(&buf[..len]);
+	Ok(decode_fn(&tmp))
+}
+
+#[inline(always)]
+fn encode_u8_raw(
+	buf: &mut [MaybeUninit<u8>; MAX_LEN_U8],
+	value: u8,
+) -> usize {
+	if value < 0x80 {
+		buf[0].write(value);
+		return 1;
+	}
+	let x = (value as u16) << 2;
+	buf[0].write(0x80 | ((x as u8) >> 2));
+	buf[1].write((x >> 8) as u8);
+	2
+}
+
+#[inline(always)]
+fn encode_u16_raw(
+	buf: &mut [MaybeUninit<u8>; MAX_LEN_U16],
+	value: u16,
+) -> usize {
+	encode_u21_raw(buf, u32::from(value))
+}
+
+// Encodes a value less than `2^21`, which covers both `u16` and `char`, in
+// at most three bytes.
+#[inline(always)]
+fn encode_u21_raw(buf: &mut [MaybeUninit<u8>; 3], value: u32) -> usize {
+	debug_assert!(value < 0x00200000);
+	let mut x = value;
+	if x < 0x80 {
+		buf[0].write(x as u8);
+		return 1;
+	}
+	if x < 0x4000 {
+		x <<= 2;
+		buf[0].write(0x80 | ((x as u8) >> 2));
+		buf[1].write((x >> 8) as u8);
+		return 2;
+	}
+	x <<= 3;
+	buf[0].write(0xC0 | ((x as u8) >> 3));
+	buf[1].write((x >> 8) as u8);
+	buf[2].write((x >> 16) as u8);
+	3
+}
+
+#[inline(always)]
+fn encode_u32_raw(
+	buf: &mut [MaybeUninit<u8>; MAX_LEN_U32],
+	value: u32,
+) -> usize {
+	let mut x = value;
+	if x < 0x80 {
+		buf[0].write(x as u8);
+		return 1;
+	}
+	if x < 0x10000000 {
+		if x < 0x00004000 {
+			x <<= 2;
+			buf[0].write(0x80 | ((x as u8) >> 2));
+			buf[1].write((x >> 8) as u8);
+			return 2;
+		}
+		if x < 0x00200000 {
+			x <<= 3;
+			buf[0].write(0xC0 | ((x as u8) >> 3));
+			buf[1].write((x >> 8) as u8);
+			buf[2].write((x >> 16) as u8);
+			return 3;
+		}
+		x <<= 4;
+		buf[0].write(0xE0 | ((x as u8) >> 4));
+		buf[1].write((x >> 8) as u8);
+		buf[2].write((x >> 16) as u8);
+		buf[3].write((x >> 24) as u8);
+		return 4;
+	}
+
+	// SAFETY: buf has a const length of `size_of::<u32>() + 1`.
+	unsafe {
+		ptr_from_mut::<[MaybeUninit<u8>; mem::size_of::<u32>() + 1]>(buf)
+			.cast::<u8>()
+			.add(1)
+			.cast::<u32>()
+			.write_unaligned(x.to_le());
+	}
+
+	buf[0].write(0xF3);
+	5
+}
+
+#[inline(always)]
+fn encode_u64_raw(
+	buf: &mut [MaybeUninit<u8>; MAX_LEN_U64],
+	value: u64,
+) -> usize {
+	let mut x = value;
+	if x < 0x80 {
+		buf[0].write(x as u8);
+		return 1;
+	}
+	if x < 0x10000000 {
+		if x < 0x00004000 {
+			x <<= 2;
+			buf[0].write(0x80 | ((x as u8) >> 2));
+			buf[1].write((x >> 8) as u8);
+			return 2;
+		}
+		if x < 0x00200000 {
+			x <<= 3;
+			buf[0].write(0xC0 | ((x as u8) >> 3));
+			buf[1].write((x >> 8) as u8);
+			buf[2].write((x >> 16) as u8);
+			return 3;
+		}
+		x <<= 4;
+		buf[0].write(0xE0 | ((x as u8) >> 4));
+		buf[1].write((x >> 8) as u8);
+		buf[2].write((x >> 16) as u8);
+		buf[3].write((x >> 24) as u8);
+		return 4;
+	}
+
+	// SAFETY: buf has a const length of `size_of::<u64>() + 1`.
+	unsafe {
+		ptr_from_mut::<[MaybeUninit<u8>; mem::size_of::<u64>() + 1]>(buf)
+			.cast::<u8>()
+			.add(1)
+			.cast::<u64>()
+			.write_unaligned(x.to_le());
+	}
+
+	const LEN_MASK: u8 = 0b111;
+	let len = ((x.leading_zeros() >> 3) as u8) ^ LEN_MASK;
+	buf[0].write(0xF0 | len);
+	(len + 2) as usize
+}
+
+#[inline(always)]
+fn encode_u128_raw(
+	buf: &mut [MaybeUninit<u8>; MAX_LEN_U128],
+	value: u128,
+) -> usize {
+	if value < 0x80 {
+		buf[0].write(value as u8);
+		return 1;
+	}
+	if value < 0x10000000 {
+		// SAFETY: A `[MaybeUninit<u8>; 17]` can be safely truncated to a
+		// `[MaybeUninit<u8>; 5]`.
+		let buf_u32 = unsafe {
+			&mut *(ptr_from_mut(buf).cast::<[MaybeUninit<u8>; MAX_LEN_U32]>())
+		};
+		return encode_u32_raw(buf_u32, value as u32);
+	}
+
+	// SAFETY: buf has a const length of `size_of::<u128>() + 1`.
+	unsafe {
+		ptr_from_mut::<[MaybeUninit<u8>; mem::size_of::<u128>() + 1]>(buf)
+			.cast::<u8>()
+			.add(1)
+			.cast::<u128>()
+			.write_unaligned(value.to_le());
+	}
+
+	const LEN_MASK: u8 = 0b1111;
+	let len = ((value.leading_zeros() >> 3) as u8) ^ LEN_MASK;
+	buf[0].write(0xF0 | len);
+	(len + 2) as usize
+}
+
+// Casts an initialized buffer to an uninitialized one, so that it can be
+// passed to the `encode_*_raw` functions. The encoders only ever write
+// initialized bytes, so the buffer remains initialized.
+#[inline(always)]
+fn as_uninit_mut<const N: usize>(
+	buf: &mut [u8; N],
+) -> &mut [MaybeUninit<u8>; N] {
+	// SAFETY: `MaybeUninit<u8>` has the same layout as `u8`, and the returned
+	// reference is only used to write initialized values.
+	unsafe { &mut *(ptr_from_mut(buf).cast::<[MaybeUninit<u8>; N]>()) }
+}
+
+// SAFETY: The caller must ensure that the first `len` elements of `buf` are
+// initialized.
+#[inline(always)]
+unsafe fn assume_init_prefix(buf: &[MaybeUninit<u8>], len: usize) -> &[u8] {
+	slice::from_raw_parts(buf.as_ptr().cast::<u8>(), len)
+}
+
+mod sealed {
+	pub trait Sealed: Sized {
+		/// Encodes `value` to `ptr`, returning the encoded length.
+		///
+		/// # Safety
+		///
+		/// `ptr` must be valid for writes of `Vu128::MAX_LEN` bytes, and those
+		/// bytes must not be accessed through any other pointer or reference
+		/// until the call returns.
+		unsafe fn encode_to_ptr(ptr: *mut u8, value: Self) -> usize;
+	}
 }
 
 #[inline(always)]
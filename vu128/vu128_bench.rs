@@ -0,0 +1,176 @@
+// Copyright (c) 2024 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+use core::convert::TryFrom;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const VALUE_COUNT: usize = 1 << 16;
+const ITERATIONS: usize = 200;
+
+struct XorShift(u64);
+
+impl XorShift {
+	fn next(&mut self) -> u64 {
+		self.0 ^= self.0 << 13;
+		self.0 ^= self.0 >> 7;
+		self.0 ^= self.0 << 17;
+		self.0
+	}
+}
+
+// Encodes values whose bit widths are drawn uniformly from `max_bits`, so
+// that every encoded length up to the maximum is represented.
+fn encode_u64_values(max_bits: u32) -> Vec<u8> {
+	let mut rng = XorShift(0x9E3779B97F4A7C15);
+	let mut values = Vec::with_capacity(VALUE_COUNT);
+	for _ in 0..VALUE_COUNT {
+		let bits = 1 + (rng.next() % u64::from(max_bits)) as u32;
+		values.push(rng.next() >> (64 - bits));
+	}
+	let mut input = vec![0u8; VALUE_COUNT * vu128::MAX_LEN_U64];
+	let len = vu128::encode_u64_slice(&values, &mut input);
+	input.truncate(len);
+	input
+}
+
+fn encode_u32_values(max_bits: u32) -> Vec<u8> {
+	let mut rng = XorShift(0x9E3779B97F4A7C15);
+	let mut values = Vec::with_capacity(VALUE_COUNT);
+	for _ in 0..VALUE_COUNT {
+		let bits = 1 + (rng.next() % u64::from(max_bits)) as u32;
+		values.push((rng.next() >> (64 - bits)) as u32);
+	}
+	let mut input = vec![0u8; VALUE_COUNT * vu128::MAX_LEN_U32];
+	let len = vu128::encode_u32_slice(&values, &mut input);
+	input.truncate(len);
+	input
+}
+
+fn bench<T: Copy + Default>(
+	name: &str,
+	input: &[u8],
+	f: impl Fn(&[u8], &mut [T]) -> usize,
+) {
+	let mut out = vec![T::default(); VALUE_COUNT];
+	let mut best = Duration::MAX;
+	for _ in 0..ITERATIONS {
+		let start = Instant::now();
+		let produced = f(black_box(input), &mut out);
+		let elapsed = start.elapsed();
+		assert_eq!(produced, VALUE_COUNT);
+		black_box(&out);
+		best = best.min(elapsed);
+	}
+	let per_value = best.as_secs_f64() * 1e9 / VALUE_COUNT as f64;
+	println!("{:<40} {:>8.3} ns/value", name, per_value);
+}
+
+// Decodes with `decode_u64`, copying values near the end of the input into
+// a zero-padded buffer.
+fn decode_u64_loop(input: &[u8], out: &mut [u64]) -> usize {
+	let mut pos = 0;
+	for slot in out.iter_mut() {
+		let rest = &input[pos..];
+		if rest.is_empty() {
+			break;
+		}
+		let fixed = rest.get(..9).and_then(|b| <&[u8; 9]>::try_from(b).ok());
+		let (value, len) = match fixed {
+			Some(buf) => vu128::decode_u64(buf),
+			None => {
+				let mut buf = [0u8; 9];
+				buf[..rest.len()].copy_from_slice(rest);
+				vu128::decode_u64(&buf)
+			},
+		};
+		*slot = value;
+		pos += len;
+	}
+	out.len()
+}
+
+fn decode_u64_from_slice_loop(input: &[u8], out: &mut [u64]) -> usize {
+	let mut pos = 0;
+	for slot in out.iter_mut() {
+		let rest = &input[pos..];
+		let (value, len) = vu128::decode_u64_from_slice(rest).unwrap();
+		*slot = value;
+		pos += len;
+	}
+	out.len()
+}
+
+fn decode_u64_slice(input: &[u8], out: &mut [u64]) -> usize {
+	vu128::decode_u64_slice(input, out).unwrap().1
+}
+
+// Decodes with `decode_u32`, copying values near the end of the input into
+// a zero-padded buffer.
+fn decode_u32_loop(input: &[u8], out: &mut [u32]) -> usize {
+	let mut pos = 0;
+	for slot in out.iter_mut() {
+		let rest = &input[pos..];
+		if rest.is_empty() {
+			break;
+		}
+		let fixed = rest.get(..5).and_then(|b| <&[u8; 5]>::try_from(b).ok());
+		let (value, len) = match fixed {
+			Some(buf) => vu128::decode_u32(buf),
+			None => {
+				let mut buf = [0u8; 5];
+				buf[..rest.len()].copy_from_slice(rest);
+				vu128::decode_u32(&buf)
+			},
+		};
+		*slot = value;
+		pos += len;
+	}
+	out.len()
+}
+
+fn decode_u32_slice(input: &[u8], out: &mut [u32]) -> usize {
+	vu128::decode_u32_slice(input, out).unwrap().1
+}
+
+// The SIMD kernels for `decode_u32_slice` require the `std` feature, so run
+// with `cargo bench --features std` to include them.
+fn main() {
+	for max_bits in [7, 14, 28, 32] {
+		let input = encode_u32_values(max_bits);
+		println!(
+			"u32 values up to {} bits ({} bytes):",
+			max_bits,
+			input.len()
+		);
+		bench("  decode_u32 loop", &input, decode_u32_loop);
+		bench("  decode_u32_slice", &input, decode_u32_slice);
+	}
+	for max_bits in [7, 28, 64] {
+		let input = encode_u64_values(max_bits);
+		println!(
+			"u64 values up to {} bits ({} bytes):",
+			max_bits,
+			input.len()
+		);
+		bench("  decode_u64 loop", &input, decode_u64_loop);
+		bench(
+			"  decode_u64_from_slice loop",
+			&input,
+			decode_u64_from_slice_loop,
+		);
+		bench("  decode_u64_slice", &input, decode_u64_slice);
+	}
+}
@@ -13,8 +13,46 @@
 //
 // SPDX-License-Identifier: 0BSD
 
+use core::convert::TryFrom;
 use core::fmt;
 
+const U8_TEST_CASES: &[(u8, &[u8])] = &[
+	(0x00, &[0x00]),
+	(0x7F, &[0x7F]),
+	(0x80, &[0b10000000, 0x02]),
+	(0xFF, &[0b10111111, 0x03]),
+];
+
+const U16_TEST_CASES: &[(u16, &[u8])] = &[
+	(0x0000, &[0x00]),
+	(0x007F, &[0x7F]),
+	(0x0080, &[0b10000000, 0x02]),
+	(0x3FFF, &[0b10111111, 0xFF]),
+	(0x4000, &[0b11000000, 0x00, 0x02]),
+	(0xFFFF, &[0b11011111, 0xFF, 0x07]),
+];
+
+const I8_TEST_CASES: &[(i8, &[u8])] = &[
+	(0, &[0x00]),
+	(-1, &[0x01]),
+	(63, &[0x7E]),
+	(-64, &[0x7F]),
+	(64, &[0x80, 0x02]),
+	(i8::MAX, &[0xBE, 0x03]),
+	(i8::MIN, &[0xBF, 0x03]),
+];
+
+const I16_TEST_CASES: &[(i16, &[u8])] = &[
+	(0, &[0x00]),
+	(-64, &[0x7F]),
+	(64, &[0x80, 0x02]),
+	(8191, &[0xBE, 0xFF]),
+	(-8192, &[0xBF, 0xFF]),
+	(8192, &[0xC0, 0x00, 0x02]),
+	(i16::MAX, &[0xDE, 0xFF, 0x07]),
+	(i16::MIN, &[0xDF, 0xFF, 0x07]),
+];
+
 const U32_TEST_CASES: &[(u32, &[u8])] = &[
 	(0xABCDE, &[0xDE, 0xE6, 0x55]),
 	(0x00000000, &[0x00000000]),
@@ -135,7 +173,7 @@ fn test_encode_u32() {
 fn test_decode_u32() {
 	for (expect, encoded_value) in U32_TEST_CASES {
 		let mut buf = [0u8; 5];
-		(&mut buf[0..encoded_value.len()]).copy_from_slice(encoded_value);
+		buf[0..encoded_value.len()].copy_from_slice(encoded_value);
 		let expect = (*expect, encoded_value.len());
 
 		for padding in [0u8, 255] {
@@ -146,6 +184,82 @@ fn test_decode_u32() {
 	}
 }
 
+#[test]
+fn test_decode_u32_overlong() {
+	let tests: &[(&[u8; 5], (u32, usize))] = &[
+		(&[0xF0, 0x12, 0xFF, 0xFF, 0xFF], (0x12, 2)),
+		(&[0xF1, 0x34, 0x12, 0xFF, 0xFF], (0x1234, 3)),
+		(&[0xF2, 0x56, 0x34, 0x12, 0xFF], (0x123456, 4)),
+		(&[0xF3, 0x78, 0x56, 0x34, 0x12], (0x12345678, 5)),
+	];
+	for (buf, expect) in tests {
+		let got = vu128::decode_u32_checked(buf);
+		assert_expected!(decode_u32_checked, &buf[..], Ok(*expect), got);
+		let got = vu128::decode_u32_from_slice(&buf[..]);
+		assert_expected!(decode_u32_from_slice, &buf[..], Ok(*expect), got);
+	}
+}
+
+#[test]
+fn test_decode_u32_checked() {
+	for (expect, encoded_value) in U32_TEST_CASES {
+		let mut buf = [0u8; 5];
+		buf[0..encoded_value.len()].copy_from_slice(encoded_value);
+		let got = vu128::decode_u32_checked(&buf);
+		let expect = Ok((*expect, encoded_value.len()));
+		assert_expected!(decode_u32_checked, encoded_value, expect, got);
+	}
+	for prefix in 0xF4..=0xFF {
+		let buf = [prefix, 0, 0, 0, 0];
+		let got = vu128::decode_u32_checked(&buf);
+		let expect = vu128::DecodeErrorKind::Overflow {
+			encoded_len: vu128::encoded_len(prefix),
+			max_len: 5,
+		};
+		assert_eq!(got.unwrap_err().kind(), expect);
+		assert!(vu128::decode_i32_checked(&buf).is_err());
+		assert!(vu128::decode_f32_checked(&buf).is_err());
+	}
+}
+
+#[test]
+fn test_decode_checked_agrees_with_from_slice() {
+	// The checked decoders only see `MAX_LEN_*` bytes, so the slice decoders
+	// must reject longer encodings even if the bytes beyond are all zero.
+	for prefix in 0x00..=0xFF {
+		for payload in [0x00, 0x01, 0xFF] {
+			let mut buf = [0u8; vu128::MAX_LEN_U128];
+			buf[0] = prefix;
+			buf[1] = payload;
+
+			let fixed = <[u8; 5]>::try_from(&buf[..5]).unwrap();
+			let checked = vu128::decode_u32_checked(&fixed);
+			let from_slice = vu128::decode_u32_from_slice(&buf);
+			assert_eq!(checked, from_slice, "{:?}", buf);
+
+			let fixed = <[u8; 9]>::try_from(&buf[..9]).unwrap();
+			let checked = vu128::decode_u64_checked(&fixed);
+			let from_slice = vu128::decode_u64_from_slice(&buf);
+			assert_eq!(checked, from_slice, "{:?}", buf);
+
+			let fixed = <[u8; 2]>::try_from(&buf[..2]).unwrap();
+			let checked = vu128::decode_u8_checked(&fixed);
+			let from_slice = vu128::decode_u8_from_slice(&buf);
+			assert_eq!(checked, from_slice, "{:?}", buf);
+
+			let fixed = <[u8; 3]>::try_from(&buf[..3]).unwrap();
+			let checked = vu128::decode_u16_checked(&fixed);
+			let from_slice = vu128::decode_u16_from_slice(&buf);
+			assert_eq!(checked, from_slice, "{:?}", buf);
+
+			let fixed = <[u8; 3]>::try_from(&buf[..3]).unwrap();
+			let checked = vu128::decode_char(&fixed);
+			let from_slice = vu128::decode_char_from_slice(&buf);
+			assert_eq!(checked, from_slice, "{:?}", buf);
+		}
+	}
+}
+
 #[test]
 fn test_encode_u64() {
 	for (value, expect) in U64_TEST_CASES {
@@ -159,7 +273,7 @@ fn test_encode_u64() {
 fn test_decode_u64() {
 	for (expect, encoded_value) in U64_TEST_CASES {
 		let mut buf = [0u8; 9];
-		(&mut buf[0..encoded_value.len()]).copy_from_slice(encoded_value);
+		buf[0..encoded_value.len()].copy_from_slice(encoded_value);
 		let expect = (*expect, encoded_value.len());
 
 		for padding in [0u8, 255] {
@@ -170,6 +284,302 @@ fn test_decode_u64() {
 	}
 }
 
+#[test]
+fn test_decode_u64_checked() {
+	for (expect, encoded_value) in U64_TEST_CASES {
+		let mut buf = [0u8; 9];
+		buf[0..encoded_value.len()].copy_from_slice(encoded_value);
+		let got = vu128::decode_u64_checked(&buf);
+		let expect = Ok((*expect, encoded_value.len()));
+		assert_expected!(decode_u64_checked, encoded_value, expect, got);
+	}
+	for prefix in 0xF8..=0xFF {
+		let buf = [prefix, 0, 0, 0, 0, 0, 0, 0, 0];
+		let got = vu128::decode_u64_checked(&buf);
+		let expect = vu128::DecodeErrorKind::Overflow {
+			encoded_len: vu128::encoded_len(prefix),
+			max_len: 9,
+		};
+		assert_eq!(got.unwrap_err().kind(), expect);
+		assert!(vu128::decode_i64_checked(&buf).is_err());
+		assert!(vu128::decode_f64_checked(&buf).is_err());
+	}
+}
+
+#[test]
+fn test_encode_padded() {
+	for (value, encoded_value) in U32_TEST_CASES {
+		for len in encoded_value.len()..=5 {
+			let mut buf = [0xFFu8; 5];
+			vu128::encode_u32_padded(&mut buf, *value, len).unwrap();
+			assert_eq!(vu128::decode_u32(&buf), (*value, len));
+			if len == encoded_value.len() {
+				let got = &buf[..len];
+				assert_expected!(encode_u32_padded, *value, *encoded_value, got);
+			}
+		}
+	}
+	for (value, encoded_value) in U64_TEST_CASES {
+		for len in encoded_value.len()..=9 {
+			let mut buf = [0xFFu8; 9];
+			vu128::encode_u64_padded(&mut buf, *value, len).unwrap();
+			assert_eq!(vu128::decode_u64(&buf), (*value, len));
+			if len == encoded_value.len() {
+				let got = &buf[..len];
+				assert_expected!(encode_u64_padded, *value, *encoded_value, got);
+			}
+		}
+		for len in encoded_value.len()..=17 {
+			let value = *value as u128;
+			let mut buf = [0xFFu8; 17];
+			vu128::encode_u128_padded(&mut buf, value, len).unwrap();
+			assert_eq!(vu128::decode_u128(&buf), (value, len));
+		}
+	}
+}
+
+#[test]
+fn test_encode_padded_errors() {
+	use vu128::EncodeError;
+
+	let mut buf = [0u8; 5];
+	assert_eq!(
+		vu128::encode_u32_padded(&mut buf, 0, 0),
+		Err(EncodeError::InvalidLength { len: 0, max_len: 5 }),
+	);
+	assert_eq!(
+		vu128::encode_u32_padded(&mut buf, 0, 6),
+		Err(EncodeError::InvalidLength { len: 6, max_len: 5 }),
+	);
+	assert_eq!(
+		vu128::encode_u32_padded(&mut buf, 0x4000, 2),
+		Err(EncodeError::ValueTooLarge { needed: 3, len: 2 }),
+	);
+
+	let mut buf = [0u8; 17];
+	assert_eq!(
+		vu128::encode_u128_padded(&mut buf, u128::MAX, 16),
+		Err(EncodeError::ValueTooLarge { needed: 17, len: 16 }),
+	);
+	assert_eq!(
+		EncodeError::ValueTooLarge { needed: 17, len: 16 }.to_string(),
+		"value too large for encoded length 16 (needed 17 bytes)",
+	);
+	assert_eq!(
+		EncodeError::InvalidPlaceholder.to_string(),
+		"placeholder not reserved by this encoder, already filled, or filled out of order",
+	);
+}
+
+#[test]
+fn test_encoder_placeholder() {
+	let mut buf = [0u8; 64];
+	let mut encoder = vu128::Encoder::new(&mut buf);
+	encoder.write_u64(1).unwrap();
+	let mut outer = encoder.reserve_u64(u64::MAX).unwrap();
+	assert_eq!((outer.offset(), outer.encoded_len()), (1, 9));
+	let outer_start = encoder.position();
+	encoder.write_u64(2).unwrap();
+	let mut inner = encoder.reserve_u64(1 << 20).unwrap();
+	let inner_start = encoder.position();
+	encoder.write_bytes(&[0xAA; 200]).unwrap_err();
+	encoder.write_bytes(&[0xAA; 10]).unwrap();
+	let inner_len = encoder.position() - inner_start;
+	inner.fill(&mut encoder, inner_len as u64).unwrap();
+	let outer_len = encoder.position() - outer_start;
+	outer.fill(&mut encoder, outer_len as u64).unwrap();
+
+	let got = encoder.finish();
+	assert_eq!(got.len(), 1 + 9 + 1 + 3 + 10);
+	assert_eq!(vu128::decode_u64_from_slice(&got[1..]), Ok((14, 9)));
+	assert_eq!(vu128::decode_u64_from_slice(&got[11..]), Ok((10, 3)));
+	assert!(vu128::decode_u64_canonical(&got[1..]).is_err());
+}
+
+#[test]
+fn test_encoder_placeholder_compact() {
+	let mut buf = [0u8; 64];
+	let mut encoder = vu128::Encoder::new(&mut buf);
+	let mut outer = encoder.reserve_u64(u64::MAX).unwrap();
+	let outer_start = encoder.position();
+	let mut inner = encoder.reserve_u64(u64::MAX).unwrap();
+	let inner_start = encoder.position();
+	encoder.write_bytes(&[0xAA; 200]).unwrap_err();
+	encoder.write_bytes(&[0xAA; 10]).unwrap();
+	let inner_len = encoder.position() - inner_start;
+	inner.fill_compact(&mut encoder, inner_len as u64).unwrap();
+	let outer_len = encoder.position() - outer_start;
+	outer.fill_compact(&mut encoder, outer_len as u64).unwrap();
+
+	let mut expect = vec![11, 10];
+	expect.extend_from_slice(&[0xAA; 10]);
+	assert_eq!(encoder.finish(), &expect[..]);
+}
+
+#[test]
+fn test_encoder_placeholder_errors() {
+	use vu128::EncodeError;
+
+	let mut buf = [0u8; 4];
+	let mut encoder = vu128::Encoder::new(&mut buf);
+	assert_eq!(
+		encoder.write_u64(u64::MAX),
+		Err(EncodeError::BufferFull { needed: 9, available: 4 }),
+	);
+	assert_eq!(
+		encoder.reserve_u64(u64::MAX),
+		Err(EncodeError::BufferFull { needed: 9, available: 4 }),
+	);
+	let mut placeholder = encoder.reserve_u64(0x7F).unwrap();
+	assert_eq!(
+		placeholder.fill(&mut encoder, 0x80),
+		Err(EncodeError::ValueTooLarge { needed: 2, len: 1 }),
+	);
+	let mut placeholder = encoder.reserve_u64(0x7F).unwrap();
+	assert_eq!(
+		placeholder.fill_compact(&mut encoder, 0x80),
+		Err(EncodeError::ValueTooLarge { needed: 2, len: 1 }),
+	);
+	assert_eq!(encoder.position(), 2);
+}
+
+#[test]
+fn test_encoder_placeholder_retry() {
+	use vu128::EncodeError;
+
+	// A failed fill leaves the placeholder and encoder unchanged, so the
+	// same slot can be filled afterwards.
+	let mut buf = [0u8; 32];
+	let mut encoder = vu128::Encoder::new(&mut buf);
+	let mut outer = encoder.reserve_u64(0xFF).unwrap();
+	let mut inner = encoder.reserve_u64(0xFF).unwrap();
+	encoder.write_bytes(b"abc").unwrap();
+	assert_eq!(
+		inner.fill_compact(&mut encoder, 0x10000),
+		Err(EncodeError::ValueTooLarge { needed: 3, len: 2 }),
+	);
+	assert_eq!(
+		inner.fill(&mut encoder, 0x10000),
+		Err(EncodeError::ValueTooLarge { needed: 3, len: 2 }),
+	);
+	assert_eq!(
+		outer.fill_compact(&mut encoder, 1),
+		Err(EncodeError::InvalidPlaceholder),
+	);
+	inner.fill_compact(&mut encoder, 3).unwrap();
+	outer.fill_compact(&mut encoder, 4).unwrap();
+	assert_eq!(encoder.finish(), &[0x04, 0x03, b'a', b'b', b'c']);
+
+	// A placeholder can only be filled once.
+	let mut buf = [0u8; 32];
+	let mut encoder = vu128::Encoder::new(&mut buf);
+	let mut placeholder = encoder.reserve_u64(0xFF).unwrap();
+	placeholder.fill(&mut encoder, 1).unwrap();
+	assert_eq!(
+		placeholder.fill(&mut encoder, 2),
+		Err(EncodeError::InvalidPlaceholder),
+	);
+	assert_eq!(
+		placeholder.fill_compact(&mut encoder, 2),
+		Err(EncodeError::InvalidPlaceholder),
+	);
+	assert_eq!(encoder.finish(), &[0x81, 0x00]);
+}
+
+#[test]
+fn test_encoder_placeholder_misuse() {
+	use vu128::EncodeError;
+
+	// A placeholder can't be filled by a different encoder.
+	let mut buf_a = [0u8; 32];
+	let mut buf_b = [0u8; 4];
+	let mut encoder_a = vu128::Encoder::new(&mut buf_a);
+	let mut encoder_b = vu128::Encoder::new(&mut buf_b);
+	encoder_a.write_bytes(&[0xAA; 4]).unwrap();
+	let mut placeholder = encoder_a.reserve_u64(u64::MAX).unwrap();
+	encoder_b.write_u64(1).unwrap();
+	assert_eq!(
+		placeholder.fill(&mut encoder_b, 1),
+		Err(EncodeError::InvalidPlaceholder),
+	);
+	let mut placeholder = encoder_a.reserve_u64(u64::MAX).unwrap();
+	assert_eq!(
+		placeholder.fill_compact(&mut encoder_b, 1),
+		Err(EncodeError::InvalidPlaceholder),
+	);
+	assert_eq!(encoder_b.finish(), &[0x01]);
+
+	// Or by a later encoder over the same buffer, even if it has reserved a
+	// placeholder at the same position.
+	let mut buf = [0u8; 32];
+	let mut encoder = vu128::Encoder::new(&mut buf);
+	let mut placeholder = encoder.reserve_u64(0xFF).unwrap();
+	let mut encoder = vu128::Encoder::new(&mut buf[..16]);
+	let mut other = encoder.reserve_u64(0xFF).unwrap();
+	assert_eq!(placeholder.offset(), other.offset());
+	assert_eq!(placeholder.encoded_len(), other.encoded_len());
+	assert_eq!(
+		placeholder.fill(&mut encoder, 1),
+		Err(EncodeError::InvalidPlaceholder),
+	);
+	assert_eq!(
+		placeholder.fill_compact(&mut encoder, 1),
+		Err(EncodeError::InvalidPlaceholder),
+	);
+	other.fill(&mut encoder, 2).unwrap();
+	let got = encoder.finish();
+	assert_eq!(got, &[0x82, 0x00]);
+
+	// Including an encoder over the output of a finished encoder.
+	let mut buf = [0u8; 32];
+	let mut encoder = vu128::Encoder::new(&mut buf);
+	let mut placeholder = encoder.reserve_u64(0xFF).unwrap();
+	let mut encoder = vu128::Encoder::new(encoder.finish());
+	let _other = encoder.reserve_u64(0xFF).unwrap();
+	assert_eq!(
+		placeholder.fill(&mut encoder, 1),
+		Err(EncodeError::InvalidPlaceholder),
+	);
+
+	// Compaction requires that later placeholders have been filled.
+	let mut buf = [0u8; 32];
+	let mut encoder = vu128::Encoder::new(&mut buf);
+	let mut outer = encoder.reserve_u64(u64::MAX).unwrap();
+	let mut inner = encoder.reserve_u64(u64::MAX).unwrap();
+	encoder.write_bytes(b"abc").unwrap();
+	assert_eq!(
+		outer.fill_compact(&mut encoder, 12),
+		Err(EncodeError::InvalidPlaceholder),
+	);
+	inner.fill_compact(&mut encoder, 3).unwrap();
+	let mut expect = vec![0xF7, 0, 0, 0, 0, 0, 0, 0, 0];
+	expect.extend_from_slice(&[0x03, b'a', b'b', b'c']);
+	assert_eq!(encoder.finish(), &expect[..]);
+
+	// Later placeholders must be filled most recent first.
+	let mut buf = [0u8; 32];
+	let mut encoder = vu128::Encoder::new(&mut buf);
+	let mut first = encoder.reserve_u64(0xFF).unwrap();
+	let mut second = encoder.reserve_u64(0xFF).unwrap();
+	let mut third = encoder.reserve_u64(0xFF).unwrap();
+	second.fill(&mut encoder, 2).unwrap();
+	third.fill(&mut encoder, 3).unwrap();
+	assert_eq!(
+		first.fill_compact(&mut encoder, 1),
+		Err(EncodeError::InvalidPlaceholder),
+	);
+
+	let mut buf = [0u8; 32];
+	let mut encoder = vu128::Encoder::new(&mut buf);
+	let mut first = encoder.reserve_u64(0xFF).unwrap();
+	let mut second = encoder.reserve_u64(0xFF).unwrap();
+	let mut third = encoder.reserve_u64(0xFF).unwrap();
+	third.fill_compact(&mut encoder, 3).unwrap();
+	second.fill(&mut encoder, 2).unwrap();
+	first.fill_compact(&mut encoder, 1).unwrap();
+	assert_eq!(encoder.finish(), &[0x01, 0x82, 0x00, 0x03]);
+}
+
 #[test]
 fn test_encode_u128() {
 	for (value, expect) in U32_TEST_CASES {
@@ -190,7 +600,7 @@ fn test_encode_u128() {
 fn test_decode_u128() {
 	for (expect, encoded_value) in U32_TEST_CASES {
 		let mut buf = [0u8; 17];
-		(&mut buf[0..encoded_value.len()]).copy_from_slice(encoded_value);
+		buf[0..encoded_value.len()].copy_from_slice(encoded_value);
 		let expect = (*expect as u128, encoded_value.len());
 
 		for padding in [0u8, 255] {
@@ -201,7 +611,7 @@ fn test_decode_u128() {
 	}
 	for (expect, encoded_value) in U64_TEST_CASES {
 		let mut buf = [0u8; 17];
-		(&mut buf[0..encoded_value.len()]).copy_from_slice(encoded_value);
+		buf[0..encoded_value.len()].copy_from_slice(encoded_value);
 		let expect = (*expect as u128, encoded_value.len());
 
 		for padding in [0u8, 255] {
@@ -225,7 +635,7 @@ fn test_encode_i32() {
 fn test_decode_i32() {
 	for (expect, encoded_value) in I32_TEST_CASES {
 		let mut buf = [0u8; 5];
-		(&mut buf[0..encoded_value.len()]).copy_from_slice(encoded_value);
+		buf[0..encoded_value.len()].copy_from_slice(encoded_value);
 		let got = vu128::decode_i32(&buf);
 		let expect = (*expect, encoded_value.len());
 		assert_expected!(decode_i32, encoded_value, expect, got);
@@ -245,7 +655,7 @@ fn test_encode_i64() {
 fn test_decode_i64() {
 	for (expect, encoded_value) in I64_TEST_CASES {
 		let mut buf = [0u8; 9];
-		(&mut buf[0..encoded_value.len()]).copy_from_slice(encoded_value);
+		buf[0..encoded_value.len()].copy_from_slice(encoded_value);
 		let got = vu128::decode_i64(&buf);
 		let expect = (*expect, encoded_value.len());
 		assert_expected!(decode_i64, encoded_value, expect, got);
@@ -272,14 +682,14 @@ fn test_encode_i128() {
 fn test_decode_i128() {
 	for (expect, encoded_value) in I32_TEST_CASES {
 		let mut buf = [0u8; 17];
-		(&mut buf[0..encoded_value.len()]).copy_from_slice(encoded_value);
+		buf[0..encoded_value.len()].copy_from_slice(encoded_value);
 		let got = vu128::decode_i128(&buf);
 		let expect = (*expect as i128, encoded_value.len());
 		assert_expected!(decode_i128, encoded_value, expect, got);
 	}
 	for (expect, encoded_value) in I64_TEST_CASES {
 		let mut buf = [0u8; 17];
-		(&mut buf[0..encoded_value.len()]).copy_from_slice(encoded_value);
+		buf[0..encoded_value.len()].copy_from_slice(encoded_value);
 		let got = vu128::decode_i128(&buf);
 		let expect = (*expect as i128, encoded_value.len());
 		assert_expected!(decode_i128, encoded_value, expect, got);
@@ -299,7 +709,7 @@ fn test_encode_f32() {
 fn test_decode_f32() {
 	for (expect, encoded_value) in F32_TEST_CASES {
 		let mut buf = [0u8; 5];
-		(&mut buf[0..encoded_value.len()]).copy_from_slice(encoded_value);
+		buf[0..encoded_value.len()].copy_from_slice(encoded_value);
 		let got = vu128::decode_f32(&buf);
 		let expect = (*expect, encoded_value.len());
 		assert_expected!(decode_f32, encoded_value, expect, got);
@@ -319,56 +729,1694 @@ fn test_encode_f64() {
 fn test_decode_f64() {
 	for (expect, encoded_value) in F64_TEST_CASES {
 		let mut buf = [0u8; 9];
-		(&mut buf[0..encoded_value.len()]).copy_from_slice(encoded_value);
+		buf[0..encoded_value.len()].copy_from_slice(encoded_value);
 		let got = vu128::decode_f64(&buf);
 		let expect = (*expect, encoded_value.len());
 		assert_expected!(decode_f64, encoded_value, expect, got);
 	}
 }
 
-trait ArgFmt: fmt::Debug {
-	fn arg_fmt(&self) -> String {
-		format!("{:?}", self)
+#[test]
+fn test_encode_u8() {
+	for (value, expect) in U8_TEST_CASES {
+		let mut buf = [0u8; 2];
+		let len = vu128::encode_u8(&mut buf, *value);
+		assert_expected!(encode_u8, *value, *expect, &buf[..len]);
+		assert_eq!(vu128::encoded_len_u8(*value), len);
 	}
 }
 
-impl ArgFmt for u32 {
-	fn arg_fmt(&self) -> String {
-		format!("0x{:08X?}", self)
+#[test]
+fn test_decode_u8() {
+	for (expect, encoded_value) in U8_TEST_CASES {
+		let mut buf = [0u8; 2];
+		buf[0..encoded_value.len()].copy_from_slice(encoded_value);
+		let expect = (*expect, encoded_value.len());
+
+		let got = vu128::decode_u8(&buf);
+		assert_expected!(decode_u8, encoded_value, expect, got);
+
+		let got = vu128::decode_u8_checked(&buf);
+		assert_expected!(decode_u8_checked, encoded_value, Ok(expect), got);
+
+		let got = vu128::decode_u8_from_slice(encoded_value);
+		assert_expected!(decode_u8_from_slice, encoded_value, Ok(expect), got);
+
+		let got = vu128::decode_u8_canonical(encoded_value);
+		assert_expected!(decode_u8_canonical, encoded_value, Ok(expect), got);
 	}
 }
 
-impl ArgFmt for i32 {}
+#[test]
+fn test_encode_u16() {
+	for (value, expect) in U16_TEST_CASES {
+		let mut buf = [0u8; 3];
+		let len = vu128::encode_u16(&mut buf, *value);
+		assert_expected!(encode_u16, *value, *expect, &buf[..len]);
+		assert_eq!(vu128::encoded_len_u16(*value), len);
+	}
+}
 
-impl ArgFmt for u64 {
-	fn arg_fmt(&self) -> String {
-		format!("0x{:016X?}", self)
+#[test]
+fn test_decode_u16() {
+	for (expect, encoded_value) in U16_TEST_CASES {
+		let mut buf = [0u8; 3];
+		buf[0..encoded_value.len()].copy_from_slice(encoded_value);
+		let expect = (*expect, encoded_value.len());
+
+		let got = vu128::decode_u16(&buf);
+		assert_expected!(decode_u16, encoded_value, expect, got);
+
+		let got = vu128::decode_u16_checked(&buf);
+		assert_expected!(decode_u16_checked, encoded_value, Ok(expect), got);
+
+		let got = vu128::decode_u16_from_slice(encoded_value);
+		assert_expected!(decode_u16_from_slice, encoded_value, Ok(expect), got);
+
+		let got = vu128::decode_u16_canonical(encoded_value);
+		assert_expected!(decode_u16_canonical, encoded_value, Ok(expect), got);
 	}
 }
-impl ArgFmt for i64 {}
 
-impl ArgFmt for u128 {
-	fn arg_fmt(&self) -> String {
-		format!("0x{:032X?}", self)
+#[test]
+fn test_encode_i8() {
+	for (value, expect) in I8_TEST_CASES {
+		let mut buf = [0u8; 2];
+		let len = vu128::encode_i8(&mut buf, *value);
+		assert_expected!(encode_i8, *value, *expect, &buf[..len]);
+		assert_eq!(vu128::encoded_len_i8(*value), len);
 	}
 }
-impl ArgFmt for i128 {}
 
-impl ArgFmt for &[u8] {
-	fn arg_fmt(&self) -> String {
-		format!("{:?}", HexArray(self))
+#[test]
+fn test_decode_i8() {
+	for (expect, encoded_value) in I8_TEST_CASES {
+		let mut buf = [0u8; 2];
+		buf[0..encoded_value.len()].copy_from_slice(encoded_value);
+		let expect = (*expect, encoded_value.len());
+
+		let got = vu128::decode_i8(&buf);
+		assert_expected!(decode_i8, encoded_value, expect, got);
+
+		let got = vu128::decode_i8_checked(&buf);
+		assert_expected!(decode_i8_checked, encoded_value, Ok(expect), got);
+
+		let got = vu128::decode_i8_from_slice(encoded_value);
+		assert_expected!(decode_i8_from_slice, encoded_value, Ok(expect), got);
+
+		let got = vu128::decode_i8_canonical(encoded_value);
+		assert_expected!(decode_i8_canonical, encoded_value, Ok(expect), got);
 	}
 }
 
-impl ArgFmt for f32 {}
+#[test]
+fn test_encode_i16() {
+	for (value, expect) in I16_TEST_CASES {
+		let mut buf = [0u8; 3];
+		let len = vu128::encode_i16(&mut buf, *value);
+		assert_expected!(encode_i16, *value, *expect, &buf[..len]);
+		assert_eq!(vu128::encoded_len_i16(*value), len);
+	}
+}
 
-impl ArgFmt for f64 {}
+#[test]
+fn test_decode_i16() {
+	for (expect, encoded_value) in I16_TEST_CASES {
+		let mut buf = [0u8; 3];
+		buf[0..encoded_value.len()].copy_from_slice(encoded_value);
+		let expect = (*expect, encoded_value.len());
 
-impl ArgFmt for usize {}
+		let got = vu128::decode_i16(&buf);
+		assert_expected!(decode_i16, encoded_value, expect, got);
 
-impl<T1: ArgFmt, T2: ArgFmt> ArgFmt for (T1, T2) {
-	fn arg_fmt(&self) -> String {
-		format!("({}, {})", self.0.arg_fmt(), self.1.arg_fmt())
+		let got = vu128::decode_i16_checked(&buf);
+		assert_expected!(decode_i16_checked, encoded_value, Ok(expect), got);
+
+		let got = vu128::decode_i16_from_slice(encoded_value);
+		assert_expected!(decode_i16_from_slice, encoded_value, Ok(expect), got);
+
+		let got = vu128::decode_i16_canonical(encoded_value);
+		assert_expected!(decode_i16_canonical, encoded_value, Ok(expect), got);
+	}
+}
+
+#[test]
+fn test_decode_small_overflow() {
+	use vu128::DecodeErrorKind;
+
+	let overflow = |encoded_len, max_len| DecodeErrorKind::Overflow {
+		encoded_len,
+		max_len,
+	};
+
+	// Values that fit in the encoded length but not in the target type.
+	let out_of_range = DecodeErrorKind::OutOfRange;
+	let err = vu128::decode_u8_checked(&[0x80, 0x04]).unwrap_err();
+	assert_eq!(err.kind(), out_of_range);
+	let err = vu128::decode_i8_from_slice(&[0x80, 0x04]).unwrap_err();
+	assert_eq!(err.kind(), out_of_range);
+	let err = vu128::decode_u16_checked(&[0xC0, 0x00, 0x10]).unwrap_err();
+	assert_eq!(err.kind(), out_of_range);
+	let err = vu128::decode_i16_from_slice(&[0xC0, 0x00, 0x10]).unwrap_err();
+	assert_eq!(err.kind(), out_of_range);
+
+	// Prefixes wider than the target buffer.
+	let err = vu128::decode_u8_checked(&[0xC0, 0x00]).unwrap_err();
+	assert_eq!(err.kind(), overflow(3, 2));
+	let err = vu128::decode_u16_checked(&[0xE0, 0x00, 0x00]).unwrap_err();
+	assert_eq!(err.kind(), overflow(4, 3));
+
+	// Over-long encodings of in-range values are accepted up to the
+	// maximum length of the target type.
+	let buf = [0x81, 0x00];
+	assert_eq!(vu128::decode_u8_from_slice(&buf), Ok((1, 2)));
+	assert!(vu128::decode_u8_canonical(&buf).is_err());
+
+	let buf = [0xF3, 0xFF, 0x00, 0x00, 0x00];
+	let err = vu128::decode_u8_from_slice(&buf).unwrap_err();
+	assert_eq!(err.kind(), overflow(5, 2));
+	let err = vu128::decode_u16_from_slice(&buf).unwrap_err();
+	assert_eq!(err.kind(), overflow(5, 3));
+	assert_eq!(vu128::decode_u32_from_slice(&buf), Ok((0xFF, 5)));
+}
+
+#[test]
+fn test_encode_usize() {
+	for (value, expect) in U64_TEST_CASES {
+		let value = match usize::try_from(*value) {
+			Ok(value) => value,
+			Err(_) => continue,
+		};
+		let mut buf = [0u8; 9];
+		let len = vu128::encode_usize(&mut buf, value);
+		assert_expected!(encode_usize, value, *expect, &buf[..len]);
+		assert_eq!(vu128::encoded_len_usize(value), len);
+	}
+}
+
+#[test]
+fn test_decode_usize() {
+	for (value, encoded_value) in U64_TEST_CASES {
+		let mut buf = [0u8; 9];
+		buf[0..encoded_value.len()].copy_from_slice(encoded_value);
+
+		let got = vu128::decode_usize_checked(&buf);
+		let got_slice = vu128::decode_usize_from_slice(encoded_value);
+		let got_canonical = vu128::decode_usize_canonical(encoded_value);
+		let value = match usize::try_from(*value) {
+			Ok(value) => value,
+			// Only reached on targets with a 32-bit `usize`, for example
+			// with `cargo test --target i686-unknown-linux-musl`.
+			Err(_) => {
+				let expect = vu128::DecodeErrorKind::OutOfRange;
+				assert_eq!(got.unwrap_err().kind(), expect);
+				assert_eq!(got_slice.unwrap_err().kind(), expect);
+				assert_eq!(got_canonical.unwrap_err().kind(), expect);
+				continue;
+			},
+		};
+
+		let expect = (value, encoded_value.len());
+		let got_unchecked = vu128::decode_usize(&buf);
+		assert_expected!(decode_usize, encoded_value, expect, got_unchecked);
+		assert_expected!(decode_usize_checked, encoded_value, Ok(expect), got);
+		assert_eq!(got_slice, Ok(expect));
+		assert_eq!(got_canonical, Ok(expect));
+	}
+}
+
+#[test]
+fn test_encode_isize() {
+	for (value, expect) in I64_TEST_CASES {
+		let value = match isize::try_from(*value) {
+			Ok(value) => value,
+			Err(_) => continue,
+		};
+		let mut buf = [0u8; 9];
+		let len = vu128::encode_isize(&mut buf, value);
+		assert_expected!(encode_isize, value, *expect, &buf[..len]);
+		assert_eq!(vu128::encoded_len_isize(value), len);
+	}
+}
+
+#[test]
+fn test_decode_isize() {
+	for (value, encoded_value) in I64_TEST_CASES {
+		let mut buf = [0u8; 9];
+		buf[0..encoded_value.len()].copy_from_slice(encoded_value);
+
+		let got = vu128::decode_isize_checked(&buf);
+		let got_slice = vu128::decode_isize_from_slice(encoded_value);
+		let got_canonical = vu128::decode_isize_canonical(encoded_value);
+		let value = match isize::try_from(*value) {
+			Ok(value) => value,
+			Err(_) => {
+				let expect = vu128::DecodeErrorKind::OutOfRange;
+				assert_eq!(got.unwrap_err().kind(), expect);
+				assert_eq!(got_slice.unwrap_err().kind(), expect);
+				assert_eq!(got_canonical.unwrap_err().kind(), expect);
+				continue;
+			},
+		};
+
+		let expect = (value, encoded_value.len());
+		let got_unchecked = vu128::decode_isize(&buf);
+		assert_expected!(decode_isize, encoded_value, expect, got_unchecked);
+		assert_expected!(decode_isize_checked, encoded_value, Ok(expect), got);
+		assert_eq!(got_slice, Ok(expect));
+		assert_eq!(got_canonical, Ok(expect));
+	}
+}
+
+#[test]
+fn test_nonzero() {
+	use core::num::{
+		NonZeroU128,
+		NonZeroU32,
+		NonZeroU64,
+	};
+
+	for (value, encoded_value) in U32_TEST_CASES {
+		let nonzero = match NonZeroU32::new(value.wrapping_add(1)) {
+			Some(nonzero) => nonzero,
+			None => continue,
+		};
+		let mut buf = [0u8; 5];
+		let len = vu128::encode_nonzero_u32(&mut buf, nonzero);
+		assert_eq!(&buf[..len], *encoded_value);
+		assert_eq!(vu128::encoded_len_nonzero_u32(nonzero), len);
+		assert_eq!(vu128::decode_nonzero_u32(&buf), Ok((nonzero, len)));
+		let got = vu128::decode_nonzero_u32_from_slice(encoded_value);
+		assert_eq!(got, Ok((nonzero, len)));
+	}
+	for (value, encoded_value) in U64_TEST_CASES {
+		let nonzero = match NonZeroU64::new(value.wrapping_add(1)) {
+			Some(nonzero) => nonzero,
+			None => continue,
+		};
+		let mut buf = [0u8; 9];
+		let len = vu128::encode_nonzero_u64(&mut buf, nonzero);
+		assert_eq!(&buf[..len], *encoded_value);
+		assert_eq!(vu128::encoded_len_nonzero_u64(nonzero), len);
+		assert_eq!(vu128::decode_nonzero_u64(&buf), Ok((nonzero, len)));
+		let got = vu128::decode_nonzero_u64_from_slice(encoded_value);
+		assert_eq!(got, Ok((nonzero, len)));
+	}
+
+	let nonzero = NonZeroU128::new(u128::MAX).unwrap();
+	let mut buf = [0u8; 17];
+	let len = vu128::encode_nonzero_u128(&mut buf, nonzero);
+	assert_eq!(vu128::decode_u128(&buf), (u128::MAX - 1, len));
+	assert_eq!(vu128::decode_nonzero_u128(&buf), Ok((nonzero, len)));
+	let got = vu128::decode_nonzero_u128_from_slice(&buf[..len]);
+	assert_eq!(got, Ok((nonzero, len)));
+}
+
+#[test]
+fn test_nonzero_overflow() {
+	use vu128::DecodeErrorKind;
+
+	let expect = DecodeErrorKind::OutOfRange;
+
+	let mut buf = [0u8; 5];
+	let _ = vu128::encode_u32(&mut buf, u32::MAX);
+	let err = vu128::decode_nonzero_u32(&buf).unwrap_err();
+	assert_eq!(err.kind(), expect);
+	let err = vu128::decode_nonzero_u32_from_slice(&buf).unwrap_err();
+	assert_eq!(err.kind(), expect);
+
+	let mut buf = [0u8; 9];
+	let _ = vu128::encode_u64(&mut buf, u64::MAX);
+	let err = vu128::decode_nonzero_u64(&buf).unwrap_err();
+	assert_eq!(err.kind(), expect);
+
+	let mut buf = [0u8; 17];
+	let _ = vu128::encode_u128(&mut buf, u128::MAX);
+	let err = vu128::decode_nonzero_u128(&buf).unwrap_err();
+	assert_eq!(err.kind(), expect);
+	let err = vu128::decode_nonzero_u128_from_slice(&buf).unwrap_err();
+	assert_eq!(err.kind(), expect);
+
+	// Wider prefixes are rejected before the value is checked.
+	let buf = [0xF4, 0, 0, 0, 0];
+	assert!(vu128::decode_nonzero_u32(&buf).is_err());
+}
+
+#[test]
+fn test_char() {
+	let tests: &[(char, &[u8])] = &[
+		('\0', &[0x00]),
+		('a', &[0x61]),
+		('\u{7F}', &[0x7F]),
+		('\u{80}', &[0x80, 0x02]),
+		('\u{3FFF}', &[0xBF, 0xFF]),
+		('\u{4000}', &[0xC0, 0x00, 0x02]),
+		('\u{D7FF}', &[0xDF, 0xBF, 0x06]),
+		('\u{E000}', &[0xC0, 0x00, 0x07]),
+		('\u{1F980}', &[0xC0, 0xCC, 0x0F]),
+		(char::MAX, &[0xDF, 0xFF, 0x87]),
+	];
+	for (value, encoded_value) in tests {
+		let mut buf = [0u8; 3];
+		let len = vu128::encode_char(&mut buf, *value);
+		assert_eq!(&buf[..len], *encoded_value);
+		assert_eq!(vu128::encoded_len_char(*value), len);
+		assert_eq!(vu128::decode_char(&buf), Ok((*value, len)));
+		let got = vu128::decode_char_from_slice(encoded_value);
+		assert_eq!(got, Ok((*value, len)));
+	}
+
+	// Every `char` is encoded as its scalar value.
+	for value in (0..=0x10FFFF).filter_map(char::from_u32) {
+		let mut buf = [0u8; 3];
+		let len = vu128::encode_char(&mut buf, value);
+		let mut buf_u32 = [0u8; 5];
+		let len_u32 = vu128::encode_u32(&mut buf_u32, value as u32);
+		assert_eq!(&buf[..len], &buf_u32[..len_u32], "{:?}", value);
+	}
+}
+
+#[test]
+fn test_char_invalid() {
+	use vu128::DecodeErrorKind;
+
+	for value in [0xD800, 0xDFFF, 0x110000, 0x1FFFFF] {
+		let mut buf = [0u8; 3];
+		let mut buf_u32 = [0u8; 5];
+		let len = vu128::encode_u32(&mut buf_u32, value);
+		buf.copy_from_slice(&buf_u32[..3]);
+
+		let expect = DecodeErrorKind::InvalidChar { value };
+		let err = vu128::decode_char(&buf).unwrap_err();
+		assert_eq!(err.kind(), expect);
+		let err = vu128::decode_char_from_slice(&buf[..len]).unwrap_err();
+		assert_eq!(err.kind(), expect);
+	}
+
+	let err = vu128::decode_char(&[0xE0, 0x00, 0x00]).unwrap_err();
+	let expect = DecodeErrorKind::Overflow {
+		encoded_len: 4,
+		max_len: 3,
+	};
+	assert_eq!(err.kind(), expect);
+
+	let buf = [0xF2, 0x00, 0x00, 0x00];
+	let err = vu128::decode_char_from_slice(&buf).unwrap_err();
+	assert_eq!(err.kind(), expect);
+
+	let mut buf = [0u8; 5];
+	let len = vu128::encode_u32(&mut buf, 0x1FFFFF);
+	let err = vu128::decode_char_from_slice(&buf[..len]).unwrap_err();
+	let expect = DecodeErrorKind::InvalidChar { value: 0x1FFFFF };
+	assert_eq!(err.kind(), expect);
+	assert_eq!(
+		err.to_string(),
+		"invalid Unicode scalar value 0x1FFFFF at offset 0",
+	);
+}
+
+#[test]
+fn test_encode_uninit() {
+	use core::mem::MaybeUninit;
+
+	for (value, expect) in U8_TEST_CASES {
+		let mut buf = [MaybeUninit::uninit(); 2];
+		assert_eq!(vu128::encode_u8_uninit(&mut buf, *value), *expect);
+	}
+	for (value, expect) in U16_TEST_CASES {
+		let mut buf = [MaybeUninit::uninit(); 3];
+		assert_eq!(vu128::encode_u16_uninit(&mut buf, *value), *expect);
+	}
+	for (value, expect) in U32_TEST_CASES {
+		let mut buf = [MaybeUninit::uninit(); 5];
+		assert_eq!(vu128::encode_u32_uninit(&mut buf, *value), *expect);
+		let mut buf = [MaybeUninit::uninit(); 17];
+		let value = *value as u128;
+		assert_eq!(vu128::encode_u128_uninit(&mut buf, value), *expect);
+	}
+	for (value, expect) in U64_TEST_CASES {
+		let mut buf = [MaybeUninit::uninit(); 9];
+		assert_eq!(vu128::encode_u64_uninit(&mut buf, *value), *expect);
+	}
+	for (value, expect) in I8_TEST_CASES {
+		let mut buf = [MaybeUninit::uninit(); 2];
+		assert_eq!(vu128::encode_i8_uninit(&mut buf, *value), *expect);
+	}
+	for (value, expect) in I16_TEST_CASES {
+		let mut buf = [MaybeUninit::uninit(); 3];
+		assert_eq!(vu128::encode_i16_uninit(&mut buf, *value), *expect);
+	}
+	for (value, expect) in I32_TEST_CASES {
+		let mut buf = [MaybeUninit::uninit(); 5];
+		assert_eq!(vu128::encode_i32_uninit(&mut buf, *value), *expect);
+		let mut buf = [MaybeUninit::uninit(); 9];
+		let value = *value as isize;
+		assert_eq!(vu128::encode_isize_uninit(&mut buf, value), *expect);
+	}
+	for (value, expect) in I64_TEST_CASES {
+		let mut buf = [MaybeUninit::uninit(); 9];
+		assert_eq!(vu128::encode_i64_uninit(&mut buf, *value), *expect);
+		let mut buf = [MaybeUninit::uninit(); 17];
+		let value = *value as i128;
+		assert_eq!(vu128::encode_i128_uninit(&mut buf, value), *expect);
+	}
+	for (value, expect) in F32_TEST_CASES {
+		let mut buf = [MaybeUninit::uninit(); 5];
+		assert_eq!(vu128::encode_f32_uninit(&mut buf, *value), *expect);
+	}
+	for (value, expect) in F64_TEST_CASES {
+		let mut buf = [MaybeUninit::uninit(); 9];
+		assert_eq!(vu128::encode_f64_uninit(&mut buf, *value), *expect);
+	}
+
+	let mut buf = [MaybeUninit::uninit(); 9];
+	assert_eq!(vu128::encode_usize_uninit(&mut buf, 12345), &[0xB9, 0xC0]);
+	let mut buf = [MaybeUninit::uninit(); 17];
+	let encoded = vu128::encode_u128_uninit(&mut buf, u128::MAX);
+	assert_eq!(encoded.len(), 17);
+	assert_eq!(vu128::decode_u128_from_slice(encoded), Ok((u128::MAX, 17)));
+}
+
+#[test]
+fn test_decoder() {
+	let mut buf = Vec::new();
+	for (_, encoded_value) in U64_TEST_CASES {
+		buf.extend_from_slice(encoded_value);
+	}
+	for (_, encoded_value) in I32_TEST_CASES {
+		buf.extend_from_slice(encoded_value);
+	}
+	buf.extend_from_slice(b"abc");
+	for (_, encoded_value) in F64_TEST_CASES {
+		buf.extend_from_slice(encoded_value);
+	}
+
+	let mut decoder = vu128::Decoder::new(&buf);
+	let mut position = 0;
+	for (value, encoded_value) in U64_TEST_CASES {
+		assert_eq!(decoder.read_u64(), Ok(*value));
+		position += encoded_value.len();
+		assert_eq!(decoder.position(), position);
+	}
+	for (value, _) in I32_TEST_CASES {
+		assert_eq!(decoder.read_i32(), Ok(*value));
+	}
+	assert_eq!(decoder.read_bytes(3), Ok(&b"abc"[..]));
+	for (value, _) in F64_TEST_CASES {
+		let got = decoder.read_vu128::<f64>().unwrap();
+		assert_eq!(got.to_bits(), value.to_bits());
+	}
+	assert_eq!(decoder.remaining(), 0);
+	assert_eq!(decoder.position(), buf.len());
+}
+
+#[test]
+fn test_decoder_errors() {
+	use vu128::DecodeErrorKind;
+
+	let buf = [0x01, 0xF4, 0x00, 0x00, 0x00, 0x00, 0x01, 0xF3, 0x00];
+	let mut decoder = vu128::Decoder::new(&buf);
+	assert_eq!(decoder.read_u32(), Ok(1));
+
+	let err = decoder.read_u32().unwrap_err();
+	let expect = DecodeErrorKind::Overflow {
+		encoded_len: 6,
+		max_len: 5,
+	};
+	assert_eq!(err.kind(), expect);
+	assert_eq!(err.offset(), 1);
+	assert_eq!(decoder.position(), 1);
+
+	assert_eq!(decoder.read_u64(), Ok(1 << 32));
+	let err = decoder.read_u32().unwrap_err();
+	let expect = DecodeErrorKind::Truncated {
+		needed: 5,
+		available: 2,
+	};
+	assert_eq!(err.kind(), expect);
+	assert_eq!(err.offset(), 7);
+
+	let err = decoder.read_bytes(3).unwrap_err();
+	let expect = DecodeErrorKind::Truncated {
+		needed: 3,
+		available: 2,
+	};
+	assert_eq!(err.kind(), expect);
+	assert_eq!(err.offset(), 7);
+	assert_eq!(decoder.remaining(), 2);
+}
+
+#[test]
+fn test_encoder() {
+	let mut expect = Vec::new();
+	for (_, encoded_value) in U64_TEST_CASES {
+		expect.extend_from_slice(encoded_value);
+	}
+	for (_, encoded_value) in I64_TEST_CASES {
+		expect.extend_from_slice(encoded_value);
+	}
+	expect.extend_from_slice(b"abc");
+	for (_, encoded_value) in F32_TEST_CASES {
+		expect.extend_from_slice(encoded_value);
+	}
+
+	// An exact-length buffer exercises both the direct and the temporary
+	// buffer paths.
+	let mut buf = vec![0u8; expect.len()];
+	let mut encoder = vu128::Encoder::new(&mut buf);
+	for (value, _) in U64_TEST_CASES {
+		encoder.write_u64(*value).unwrap();
+	}
+	for (value, _) in I64_TEST_CASES {
+		encoder.write_i128(i128::from(*value)).unwrap();
+	}
+	encoder.write_bytes(b"abc").unwrap();
+	for (value, _) in F32_TEST_CASES {
+		encoder.write_f32(*value).unwrap();
+	}
+	assert_eq!(encoder.remaining(), 0);
+	assert_eq!(encoder.position(), expect.len());
+	assert_eq!(encoder.finish(), &expect[..]);
+}
+
+#[test]
+fn test_encoder_errors() {
+	use vu128::EncodeError;
+
+	let mut buf = [0u8; 6];
+	let mut encoder = vu128::Encoder::new(&mut buf);
+	encoder.write_u32(1).unwrap();
+
+	let err = encoder.write_u64(u64::MAX).unwrap_err();
+	let expect = EncodeError::BufferFull {
+		needed: 9,
+		available: 5,
+	};
+	assert_eq!(err, expect);
+	assert_eq!(encoder.position(), 1);
+
+	encoder.write_u64(u64::from(u32::MAX)).unwrap();
+	let err = encoder.write_bytes(b"a").unwrap_err();
+	let expect = EncodeError::BufferFull {
+		needed: 1,
+		available: 0,
+	};
+	assert_eq!(err, expect);
+	assert_eq!(encoder.finish(), &[0x01, 0xF3, 0xFF, 0xFF, 0xFF, 0xFF]);
+}
+
+#[test]
+fn test_decode_iter() {
+	let mut buf = Vec::new();
+	for (_, encoded_value) in U64_TEST_CASES {
+		buf.extend_from_slice(encoded_value);
+	}
+	let got: Result<Vec<u64>, _> = vu128::iter_u64(&buf).collect();
+	let expect: Vec<u64> = U64_TEST_CASES.iter().map(|(v, _)| *v).collect();
+	assert_eq!(got, Ok(expect));
+
+	let mut buf = Vec::new();
+	for (_, encoded_value) in I32_TEST_CASES {
+		buf.extend_from_slice(encoded_value);
+	}
+	let mut values = vu128::iter_i32(&buf);
+	for (value, _) in I32_TEST_CASES {
+		assert_eq!(values.next(), Some(Ok(*value)));
+	}
+	assert_eq!(values.position(), buf.len());
+	assert_eq!(values.size_hint(), (0, Some(0)));
+	assert_eq!(values.next(), None);
+
+	assert_eq!(vu128::iter_u8(&[]).next(), None);
+}
+
+#[test]
+fn test_decode_iter_errors() {
+	use vu128::DecodeErrorKind;
+
+	// A value that overflows the target type stops iteration.
+	let buf = [0x01, 0x80, 0x04, 0x02];
+	let mut values = vu128::iter_u8(&buf);
+	assert_eq!(values.next(), Some(Ok(1)));
+	let err = values.next().unwrap().unwrap_err();
+	assert_eq!(err.kind(), DecodeErrorKind::OutOfRange);
+	assert_eq!(err.offset(), 1);
+	assert_eq!(values.size_hint(), (0, Some(0)));
+	assert_eq!(values.next(), None);
+	assert_eq!(values.next(), None);
+
+	// A truncated trailing value is reported as an error.
+	let buf = [0x01, 0x02, 0xC0, 0x00];
+	let mut values = vu128::iter_u32(&buf);
+	assert_eq!(values.size_hint(), (1, Some(4)));
+	assert_eq!(values.next(), Some(Ok(1)));
+	assert_eq!(values.next(), Some(Ok(2)));
+	let err = values.next().unwrap().unwrap_err();
+	let expect = DecodeErrorKind::Truncated {
+		needed: 3,
+		available: 2,
+	};
+	assert_eq!(err.kind(), expect);
+	assert_eq!(err.offset(), 2);
+	assert_eq!(values.next(), None);
+}
+
+#[test]
+fn test_encode_iter() {
+	let mut expect = Vec::new();
+	for (_, encoded_value) in U64_TEST_CASES {
+		expect.extend_from_slice(encoded_value);
+	}
+	let values = U64_TEST_CASES.iter().map(|(value, _)| *value);
+	let iter = vu128::encode_iter(values);
+	let (lower, upper) = iter.size_hint();
+	assert_eq!(lower, U64_TEST_CASES.len());
+	assert_eq!(upper, Some(U64_TEST_CASES.len() * 9));
+	let got: Vec<u8> = iter.collect();
+	assert_eq!(got, expect);
+
+	let mut iter = vu128::encode_iter([u128::MAX]);
+	assert_eq!(iter.next(), Some(0xFF));
+	assert_eq!(iter.size_hint(), (16, Some(16)));
+	assert_eq!(iter.by_ref().count(), 16);
+	assert_eq!(iter.next(), None);
+
+	assert_eq!(vu128::encode_iter(Vec::<i32>::new()).next(), None);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_encode_iter_read() {
+	use std::io::Read;
+
+	let mut expect = Vec::new();
+	for (_, encoded_value) in I64_TEST_CASES {
+		expect.extend_from_slice(encoded_value);
+	}
+	let values = || I64_TEST_CASES.iter().map(|(value, _)| *value);
+
+	let mut got = Vec::new();
+	vu128::encode_iter(values()).read_to_end(&mut got).unwrap();
+	assert_eq!(got, expect);
+
+	// Small reads split values across calls.
+	for chunk_len in 1..=10 {
+		let mut iter = vu128::encode_iter(values());
+		let mut got = Vec::new();
+		let mut chunk = vec![0u8; chunk_len];
+		loop {
+			let n = iter.read(&mut chunk).unwrap();
+			if n == 0 {
+				break;
+			}
+			got.extend_from_slice(&chunk[..n]);
+		}
+		assert_eq!(got, expect);
+	}
+
+	let mut out = Vec::new();
+	let copied = std::io::copy(&mut vu128::encode_iter(values()), &mut out);
+	assert_eq!(copied.unwrap(), expect.len() as u64);
+	assert_eq!(out, expect);
+}
+
+#[test]
+fn test_encode_slice() {
+	let values: Vec<u64> = U64_TEST_CASES.iter().map(|(v, _)| *v).collect();
+	let mut expect = Vec::new();
+	for (_, encoded_value) in U64_TEST_CASES {
+		expect.extend_from_slice(encoded_value);
+	}
+
+	// Buffers from the exact length up to the worst case exercise each of
+	// the encoding loops.
+	for out_len in expect.len()..=values.len() * 9 {
+		let mut out = vec![0u8; out_len];
+		let len = vu128::encode_u64_slice(&values, &mut out);
+		assert_eq!(&out[..len], &expect[..]);
+	}
+
+	let values: Vec<u32> = U32_TEST_CASES.iter().map(|(v, _)| *v).collect();
+	let mut expect = Vec::new();
+	for (_, encoded_value) in U32_TEST_CASES {
+		expect.extend_from_slice(encoded_value);
+	}
+	let mut out = vec![0u8; values.len() * 5];
+	let len = vu128::encode_u32_slice(&values, &mut out);
+	assert_eq!(&out[..len], &expect[..]);
+	let wide: Vec<u128> = values.iter().map(|v| u128::from(*v)).collect();
+	let mut out = vec![0u8; expect.len()];
+	let len = vu128::encode_u128_slice(&wide, &mut out);
+	assert_eq!(&out[..len], &expect[..]);
+
+	let values: Vec<i32> = I32_TEST_CASES.iter().map(|(v, _)| *v).collect();
+	let mut expect = Vec::new();
+	for (_, encoded_value) in I32_TEST_CASES {
+		expect.extend_from_slice(encoded_value);
+	}
+	let mut out = vec![0u8; values.len() * 5];
+	let len = vu128::encode_i32_slice(&values, &mut out);
+	assert_eq!(&out[..len], &expect[..]);
+
+	let values: Vec<i64> = I64_TEST_CASES.iter().map(|(v, _)| *v).collect();
+	let mut expect = Vec::new();
+	for (_, encoded_value) in I64_TEST_CASES {
+		expect.extend_from_slice(encoded_value);
+	}
+	let mut out = vec![0u8; expect.len()];
+	let len = vu128::encode_i64_slice(&values, &mut out);
+	assert_eq!(&out[..len], &expect[..]);
+	let wide: Vec<i128> = values.iter().map(|v| i128::from(*v)).collect();
+	let mut out = vec![0u8; values.len() * 17];
+	let len = vu128::encode_i128_slice(&wide, &mut out);
+	assert_eq!(&out[..len], &expect[..]);
+
+	assert_eq!(vu128::encode_u64_slice(&[], &mut []), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_encode_slice_too_small() {
+	let mut out = [0u8; 4];
+	vu128::encode_u32_slice(&[1, 2, 3, 0x10000000], &mut out);
+}
+
+#[test]
+fn test_encode_slice_too_small_unmodified() {
+	use std::panic;
+
+	let values = [1u64; 40];
+	let mut out = [0xAAu8; 39];
+	let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+		vu128::encode_u64_slice(&values, &mut out)
+	}));
+	assert!(result.is_err());
+	assert_eq!(out, [0xAA; 39]);
+
+	// Only the last value does not fit.
+	let mut values = [1u32; 9];
+	values[8] = u32::MAX;
+	let mut out = [0xAAu8; 12];
+	let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+		vu128::encode_u32_slice(&values, &mut out)
+	}));
+	assert!(result.is_err());
+	assert_eq!(out, [0xAA; 12]);
+}
+
+#[test]
+fn test_decode_slice() {
+	let mut input = Vec::new();
+	for (_, encoded_value) in U64_TEST_CASES {
+		input.extend_from_slice(encoded_value);
+	}
+	let expect: Vec<u64> = U64_TEST_CASES.iter().map(|(v, _)| *v).collect();
+
+	let mut out = vec![0u64; expect.len() + 1];
+	let got = vu128::decode_u64_slice(&input, &mut out);
+	assert_eq!(got, Ok((input.len(), expect.len())));
+	assert_eq!(&out[..expect.len()], &expect[..]);
+
+	// Decoding stops when the output is full.
+	let mut out = [0u64; 3];
+	let consumed = U64_TEST_CASES[..3].iter().map(|(_, e)| e.len()).sum();
+	let got = vu128::decode_u64_slice(&input, &mut out);
+	assert_eq!(got, Ok((consumed, 3)));
+	assert_eq!(&out[..], &expect[..3]);
+
+	// Decoding stops before a partial trailing value.
+	for input_len in 0..=input.len() {
+		let mut out = vec![0u64; expect.len()];
+		let (consumed, produced) =
+			vu128::decode_u64_slice(&input[..input_len], &mut out).unwrap();
+		let mut expect_consumed = 0;
+		let mut expect_produced = 0;
+		for (_, encoded_value) in U64_TEST_CASES {
+			if expect_consumed + encoded_value.len() > input_len {
+				break;
+			}
+			expect_consumed += encoded_value.len();
+			expect_produced += 1;
+		}
+		assert_eq!((consumed, produced), (expect_consumed, expect_produced));
+		assert_eq!(&out[..produced], &expect[..produced]);
+	}
+
+	let mut input = Vec::new();
+	for (_, encoded_value) in I32_TEST_CASES {
+		input.extend_from_slice(encoded_value);
+	}
+	let expect: Vec<i32> = I32_TEST_CASES.iter().map(|(v, _)| *v).collect();
+	let mut out = vec![0i32; expect.len()];
+	let got = vu128::decode_i32_slice(&input, &mut out);
+	assert_eq!(got, Ok((input.len(), expect.len())));
+	assert_eq!(out, expect);
+	let mut out = vec![0i128; expect.len()];
+	let got = vu128::decode_i128_slice(&input, &mut out);
+	assert_eq!(got, Ok((input.len(), expect.len())));
+	let wide: Vec<i128> = expect.iter().map(|v| i128::from(*v)).collect();
+	assert_eq!(out, wide);
+}
+
+#[test]
+fn test_decode_slice_errors() {
+	use vu128::DecodeErrorKind;
+
+	// An encoding longer than `MAX_LEN_U32` is rejected even if the value
+	// would fit.
+	let input = [0x01, 0xF4, 0x02, 0x00, 0x00, 0x00, 0x00, 0x03];
+	let mut out = [0u32; 4];
+	let err = vu128::decode_u32_slice(&input, &mut out).unwrap_err();
+	let expect = DecodeErrorKind::Overflow {
+		encoded_len: 6,
+		max_len: 5,
+	};
+	assert_eq!(err.error().kind(), expect);
+	assert_eq!(err.error().offset(), 1);
+	assert_eq!(err.produced(), 1);
+
+	let input = [0xF0, 0x05, 0x07, 0x08, 0x09];
+	let mut out = [0u32; 4];
+	let got = vu128::decode_u32_slice(&input, &mut out);
+	assert_eq!(got, Ok((5, 4)));
+	assert_eq!(out, [5, 7, 8, 9]);
+	let mut out = [0i32; 4];
+	let got = vu128::decode_i32_slice(&input, &mut out);
+	assert_eq!(got, Ok((5, 4)));
+	assert_eq!(out, [-3, -4, 4, -5]);
+
+	let input = [0x01, 0xF4, 0x00, 0x00, 0x00, 0x00, 0x01, 0x03];
+	let mut out = [0u32; 4];
+	let err = vu128::decode_u32_slice(&input, &mut out).unwrap_err();
+	let expect = DecodeErrorKind::Overflow {
+		encoded_len: 6,
+		max_len: 5,
+	};
+	assert_eq!(err.error().kind(), expect);
+	assert_eq!(err.error().offset(), 1);
+	assert_eq!(err.produced(), 1);
+	assert_eq!(out[0], 1);
+	assert_eq!(vu128::DecodeError::from(err), err.error());
+}
+
+// Decodes `u32` values one at a time, for comparison with the bulk decoder.
+fn decode_u32_slice_scalar(
+	input: &[u8],
+	out: &mut [u32],
+) -> DecodeU32SliceResult {
+	let mut pos = 0;
+	let mut produced = 0;
+	while produced < out.len() && pos < input.len() {
+		if vu128::encoded_len(input[pos]) > input.len() - pos {
+			break;
+		}
+		match vu128::decode_u32_from_slice(&input[pos..]) {
+			Ok((value, len)) => {
+				out[produced] = value;
+				produced += 1;
+				pos += len;
+			},
+			Err(err) => {
+				return Err((err.kind(), pos + err.offset(), produced));
+			},
+		}
+	}
+	Ok((pos, produced))
+}
+
+type DecodeU32SliceResult =
+	Result<(usize, usize), (vu128::DecodeErrorKind, usize, usize)>;
+
+fn decode_u32_slice_bulk(
+	input: &[u8],
+	out: &mut [u32],
+) -> DecodeU32SliceResult {
+	slice_error_parts(vu128::decode_u32_slice(input, out))
+}
+
+fn slice_error_parts(
+	decoded: Result<(usize, usize), vu128::DecodeSliceError>,
+) -> DecodeU32SliceResult {
+	decoded.map_err(|err| {
+		let error = err.error();
+		(error.kind(), error.offset(), err.produced())
+	})
+}
+
+struct XorShift(u64);
+
+impl XorShift {
+	fn next(&mut self) -> u64 {
+		self.0 ^= self.0 << 13;
+		self.0 ^= self.0 >> 7;
+		self.0 ^= self.0 << 17;
+		self.0
+	}
+}
+
+#[test]
+fn test_decode_u32_slice_differential() {
+	check_decode_u32_slice(decode_u32_slice_bulk);
+}
+
+// Compares the SIMD kernels with the scalar decoder. Each is only checked if
+// the CPU supports it.
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+#[test]
+fn test_decode_u32_slice_x86_kernels() {
+	use vu128::__X86Kernel as Kernel;
+
+	fn decode_sse41(input: &[u8], out: &mut [u32]) -> DecodeU32SliceResult {
+		let decoded = vu128::__decode_u32_slice_x86(Kernel::Sse41, input, out);
+		slice_error_parts(decoded.unwrap())
+	}
+
+	fn decode_avx2(input: &[u8], out: &mut [u32]) -> DecodeU32SliceResult {
+		let decoded = vu128::__decode_u32_slice_x86(Kernel::Avx2, input, out);
+		slice_error_parts(decoded.unwrap())
+	}
+
+	if std::is_x86_feature_detected!("sse4.1") {
+		check_decode_u32_slice(decode_sse41);
+	}
+	if std::is_x86_feature_detected!("avx2") {
+		check_decode_u32_slice(decode_avx2);
+	}
+}
+
+fn check_decode_u32_slice(
+	decode: fn(&[u8], &mut [u32]) -> DecodeU32SliceResult,
+) {
+	let mut rng = XorShift(0x9E3779B97F4A7C15);
+
+	// Each pass draws values from a different mix of encoded lengths, so that
+	// runs of single-byte values, of unary prefixes, and of binary prefixes
+	// are all exercised.
+	let value_bits: &[&[u32]] = &[
+		&[7],
+		&[14],
+		&[7, 14, 21, 28],
+		&[7, 7, 7, 14, 21, 28, 32],
+		&[28, 32],
+	];
+	for bits in value_bits {
+		let mut input = Vec::new();
+		let mut values = Vec::new();
+		for _ in 0..1000 {
+			let random = rng.next();
+			let bits = bits[(random % bits.len() as u64) as usize];
+			let value = ((random >> 32) as u32) >> (32 - bits);
+			values.push(value);
+
+			// Some values are given over-long encodings, a few of them
+			// longer than the maximum length of a `u32`.
+			let mut buf = [0u8; vu128::MAX_LEN_U128];
+			let min_len = vu128::encoded_len_u32(value);
+			let mut len = min_len;
+			if (random >> 8) & 0b111 == 0 {
+				len += (random >> 16) as usize
+					% (vu128::MAX_LEN_U32 - min_len + 1);
+			} else if (random >> 8) & 0b111111 == 1 {
+				len += (random >> 16) as usize % (buf.len() - min_len + 1);
+			}
+			vu128::encode_u128_padded(&mut buf, value.into(), len).unwrap();
+			input.extend_from_slice(&buf[..len]);
+		}
+
+		for out_len in [0, 1, 3, 4, 15, 16, 17, 33, 999, 1000, 1001] {
+			let mut got = vec![0u32; out_len];
+			let mut expect = vec![0u32; out_len];
+			assert_eq!(
+				decode(&input, &mut got),
+				decode_u32_slice_scalar(&input, &mut expect),
+			);
+			assert_eq!(got, expect);
+		}
+
+		for _ in 0..100 {
+			let start = rng.next() as usize % input.len();
+			let end = start + rng.next() as usize % (input.len() - start);
+			let input = &input[start..end];
+			let mut got = vec![0u32; values.len()];
+			let mut expect = vec![0u32; values.len()];
+			let decoded = decode(input, &mut got);
+			assert_eq!(decoded, decode_u32_slice_scalar(input, &mut expect));
+			let produced = match decoded {
+				Ok((_, produced)) => produced,
+				Err(_) => 0,
+			};
+			assert_eq!(&got[..produced], &expect[..produced]);
+		}
+
+		// Values that overflow are reported at the same offset.
+		for _ in 0..100 {
+			let mut input = input.clone();
+			let pos = rng.next() as usize % input.len();
+			let overflow = [0xF4, 0x00, 0x00, 0x00, 0x00, 0x01];
+			input.splice(pos..pos, overflow.iter().copied());
+			let mut got = vec![0u32; values.len() + 1];
+			let mut expect = vec![0u32; values.len() + 1];
+			assert_eq!(
+				decode(&input, &mut got),
+				decode_u32_slice_scalar(&input, &mut expect),
+			);
+		}
+	}
+}
+
+#[test]
+fn test_encoded_len_value() {
+	for (value, encoded_value) in U32_TEST_CASES {
+		assert_eq!(vu128::encoded_len_u32(*value), encoded_value.len());
+		let value = *value as u128;
+		assert_eq!(vu128::encoded_len_u128(value), encoded_value.len());
+	}
+	for (value, encoded_value) in U64_TEST_CASES {
+		assert_eq!(vu128::encoded_len_u64(*value), encoded_value.len());
+		let value = *value as u128;
+		assert_eq!(vu128::encoded_len_u128(value), encoded_value.len());
+	}
+	for (value, encoded_value) in I32_TEST_CASES {
+		assert_eq!(vu128::encoded_len_i32(*value), encoded_value.len());
+	}
+	for (value, encoded_value) in I64_TEST_CASES {
+		assert_eq!(vu128::encoded_len_i64(*value), encoded_value.len());
+		let value = *value as i128;
+		assert_eq!(vu128::encoded_len_i128(value), encoded_value.len());
+	}
+	for (value, encoded_value) in F32_TEST_CASES {
+		assert_eq!(vu128::encoded_len_f32(*value), encoded_value.len());
+	}
+	for (value, encoded_value) in F64_TEST_CASES {
+		assert_eq!(vu128::encoded_len_f64(*value), encoded_value.len());
+	}
+	assert_eq!(vu128::encoded_len_u128(u128::MAX), 17);
+	assert_eq!(vu128::encoded_len_i128(i128::MIN), 17);
+}
+
+#[test]
+fn test_decode_u32_from_slice() {
+	for (expect, encoded_value) in U32_TEST_CASES {
+		let mut buf = encoded_value.to_vec();
+		let expect = Ok((*expect, encoded_value.len()));
+
+		let got = vu128::decode_u32_from_slice(&buf);
+		assert_expected!(decode_u32_from_slice, encoded_value, expect, got);
+
+		buf.push(0xFF);
+		let got = vu128::decode_u32_from_slice(&buf);
+		assert_expected!(decode_u32_from_slice, encoded_value, expect, got);
+	}
+}
+
+#[test]
+fn test_decode_u64_from_slice() {
+	for (expect, encoded_value) in U64_TEST_CASES {
+		let mut buf = encoded_value.to_vec();
+		let expect = Ok((*expect, encoded_value.len()));
+
+		let got = vu128::decode_u64_from_slice(&buf);
+		assert_expected!(decode_u64_from_slice, encoded_value, expect, got);
+
+		buf.push(0xFF);
+		let got = vu128::decode_u64_from_slice(&buf);
+		assert_expected!(decode_u64_from_slice, encoded_value, expect, got);
+	}
+}
+
+#[test]
+fn test_decode_u128_from_slice() {
+	for (expect, encoded_value) in U64_TEST_CASES {
+		let mut buf = encoded_value.to_vec();
+		let expect = Ok((*expect as u128, encoded_value.len()));
+
+		let got = vu128::decode_u128_from_slice(&buf);
+		assert_expected!(decode_u128_from_slice, encoded_value, expect, got);
+
+		buf.push(0xFF);
+		let got = vu128::decode_u128_from_slice(&buf);
+		assert_expected!(decode_u128_from_slice, encoded_value, expect, got);
+	}
+}
+
+#[test]
+fn test_decode_i32_from_slice() {
+	for (expect, encoded_value) in I32_TEST_CASES {
+		let got = vu128::decode_i32_from_slice(encoded_value);
+		let expect = Ok((*expect, encoded_value.len()));
+		assert_expected!(decode_i32_from_slice, encoded_value, expect, got);
+	}
+}
+
+#[test]
+fn test_decode_i64_from_slice() {
+	for (expect, encoded_value) in I64_TEST_CASES {
+		let got = vu128::decode_i64_from_slice(encoded_value);
+		let expect = Ok((*expect, encoded_value.len()));
+		assert_expected!(decode_i64_from_slice, encoded_value, expect, got);
+	}
+}
+
+#[test]
+fn test_decode_i128_from_slice() {
+	for (expect, encoded_value) in I64_TEST_CASES {
+		let got = vu128::decode_i128_from_slice(encoded_value);
+		let expect = Ok((*expect as i128, encoded_value.len()));
+		assert_expected!(decode_i128_from_slice, encoded_value, expect, got);
+	}
+}
+
+#[test]
+fn test_decode_f32_from_slice() {
+	for (expect, encoded_value) in F32_TEST_CASES {
+		let got = vu128::decode_f32_from_slice(encoded_value);
+		let expect = Ok((*expect, encoded_value.len()));
+		assert_expected!(decode_f32_from_slice, encoded_value, expect, got);
+	}
+}
+
+#[test]
+fn test_decode_f64_from_slice() {
+	for (expect, encoded_value) in F64_TEST_CASES {
+		let got = vu128::decode_f64_from_slice(encoded_value);
+		let expect = Ok((*expect, encoded_value.len()));
+		assert_expected!(decode_f64_from_slice, encoded_value, expect, got);
+	}
+}
+
+#[test]
+fn test_decode_from_slice_truncated() {
+	use vu128::DecodeErrorKind;
+
+	assert_eq!(
+		vu128::decode_u32_from_slice(&[]).unwrap_err().kind(),
+		DecodeErrorKind::Truncated { needed: 1, available: 0 },
+	);
+	for (_, encoded_value) in U64_TEST_CASES {
+		let len = encoded_value.len();
+		if len == 1 {
+			continue;
+		}
+		let expect = DecodeErrorKind::Truncated {
+			needed: len,
+			available: len - 1,
+		};
+		let got = vu128::decode_u64_from_slice(&encoded_value[..len - 1]);
+		assert_eq!(got.unwrap_err().kind(), expect);
+	}
+}
+
+#[test]
+fn test_decode_from_slice_overflow() {
+	use vu128::DecodeErrorKind;
+
+	// Encodings longer than the type's maximum are rejected even if the
+	// extra bytes are zero.
+	let buf = [0xF7, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+	let expect = DecodeErrorKind::Overflow {
+		encoded_len: 9,
+		max_len: 5,
+	};
+	assert_eq!(vu128::decode_u32_from_slice(&buf).unwrap_err().kind(), expect);
+	assert_eq!(vu128::decode_i32_from_slice(&buf).unwrap_err().kind(), expect);
+
+	let buf = [0xF4, 0x00, 0x00, 0x00, 0x00, 0x01];
+	let expect = DecodeErrorKind::Overflow {
+		encoded_len: 6,
+		max_len: 5,
+	};
+	assert_eq!(vu128::decode_u32_from_slice(&buf).unwrap_err().kind(), expect);
+	assert_eq!(vu128::decode_f32_from_slice(&buf).unwrap_err().kind(), expect);
+
+	let mut buf = [0u8; 17];
+	buf[0] = 0xFF;
+	buf[16] = 0x01;
+	assert_eq!(
+		vu128::decode_u64_from_slice(&buf).unwrap_err().kind(),
+		DecodeErrorKind::Overflow {
+			encoded_len: 17,
+			max_len: 9,
+		},
+	);
+	assert_eq!(vu128::decode_u128_from_slice(&buf), Ok((1 << 120, 17)));
+}
+
+#[test]
+fn test_decode_canonical() {
+	for (expect, encoded_value) in U32_TEST_CASES {
+		let got = vu128::decode_u32_canonical(encoded_value);
+		let expect = Ok((*expect, encoded_value.len()));
+		assert_expected!(decode_u32_canonical, encoded_value, expect, got);
+	}
+	for (expect, encoded_value) in U64_TEST_CASES {
+		let got = vu128::decode_u64_canonical(encoded_value);
+		let expect = Ok((*expect, encoded_value.len()));
+		assert_expected!(decode_u64_canonical, encoded_value, expect, got);
+
+		let got = vu128::decode_u128_canonical(encoded_value);
+		let expect = expect.map(|(value, len)| (value as u128, len));
+		assert_expected!(decode_u128_canonical, encoded_value, expect, got);
+	}
+	for (expect, encoded_value) in I64_TEST_CASES {
+		let got = vu128::decode_i64_canonical(encoded_value);
+		let expect = Ok((*expect, encoded_value.len()));
+		assert_expected!(decode_i64_canonical, encoded_value, expect, got);
+	}
+	for (expect, encoded_value) in F64_TEST_CASES {
+		let got = vu128::decode_f64_canonical(encoded_value);
+		let expect = Ok((*expect, encoded_value.len()));
+		assert_expected!(decode_f64_canonical, encoded_value, expect, got);
+	}
+}
+
+#[test]
+fn test_decode_non_canonical() {
+	use vu128::NonCanonicalReason::*;
+
+	let tests: &[(&[u8], vu128::NonCanonicalReason)] = &[
+		(&[0x81, 0x00], LengthPrefix),
+		(&[0xC0, 0x02, 0x00], LengthPrefix),
+		(&[0xE0, 0x00, 0x00, 0x00], LengthPrefix),
+		(&[0xF0, 0x7F], BinaryLengthPrefix),
+		(&[0xF3, 0x00, 0x00, 0x00, 0x00], BinaryLengthPrefix),
+		(&[0xC0, 0x04, 0x00], ZeroPadding),
+		(&[0xE0, 0x00, 0x40, 0x00], ZeroPadding),
+		(&[0xF4, 0x00, 0x00, 0x00, 0x10, 0x00], ZeroPadding),
+		(&[0xF0, 0x80], BinaryLengthPrefix),
+		(&[0xF3, 0xFF, 0xFF, 0xFF, 0x0F], BinaryLengthPrefix),
+	];
+	for (encoded_value, reason) in tests {
+		let expect = vu128::DecodeErrorKind::NonCanonical(*reason);
+		let got = vu128::decode_u64_canonical(encoded_value).unwrap_err();
+		assert_eq!(got.kind(), expect, "{:?}", encoded_value);
+		if encoded_value.len() <= vu128::MAX_LEN_U32 {
+			let got = vu128::decode_i32_canonical(encoded_value).unwrap_err();
+			assert_eq!(got.kind(), expect, "{:?}", encoded_value);
+		}
+
+		// The tolerant decoder accepts over-long encodings.
+		assert!(vu128::decode_u64_from_slice(encoded_value).is_ok());
+	}
+}
+
+#[test]
+fn test_is_canonical() {
+	for (_, encoded_value) in U32_TEST_CASES {
+		assert!(vu128::is_canonical_u32(encoded_value));
+	}
+	for (_, encoded_value) in U64_TEST_CASES {
+		assert!(vu128::is_canonical_u64(encoded_value));
+		assert!(vu128::is_canonical_u128(encoded_value));
+	}
+	assert!(!vu128::is_canonical_u32(&[]));
+	assert!(!vu128::is_canonical_u32(&[0x81, 0x00]));
+	assert!(!vu128::is_canonical_u32(&[0x01, 0x00]));
+	assert!(!vu128::is_canonical_u32(&[0xF4, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]));
+	assert!(vu128::is_canonical_u64(&[0xF4, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]));
+}
+
+#[test]
+fn test_canonicalize() {
+	let mut buf = Vec::new();
+	let mut expect = Vec::new();
+	for (value, encoded_value) in U64_TEST_CASES {
+		// Over-long encoding using the widest binary prefix.
+		buf.push(0xFF);
+		buf.extend_from_slice(&(*value as u128).to_le_bytes());
+
+		buf.extend_from_slice(encoded_value);
+		expect.extend_from_slice(encoded_value);
+		expect.extend_from_slice(encoded_value);
+	}
+
+	let len = vu128::canonicalize(&mut buf).unwrap();
+	assert_eq!(&buf[..len], &expect[..]);
+
+	let mut buf = [0x00, 0x81, 0x00, 0xF3, 0x00];
+	let err = vu128::canonicalize(&mut buf).unwrap_err();
+	assert_eq!(err.offset(), 3);
+	assert_eq!(
+		err.kind(),
+		vu128::DecodeErrorKind::Truncated {
+			needed: 5,
+			available: 2,
+		},
+	);
+}
+
+#[cfg(feature = "std")]
+struct OneByteReader<'a>(&'a [u8]);
+
+#[cfg(feature = "std")]
+impl std::io::Read for OneByteReader<'_> {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		if buf.is_empty() || self.0.is_empty() {
+			return Ok(0);
+		}
+		buf[0] = self.0[0];
+		self.0 = &self.0[1..];
+		Ok(1)
+	}
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_io_ext() {
+	use vu128::{
+		ReadVu128Ext,
+		WriteVu128Ext,
+	};
+
+	let mut out = Vec::new();
+	for (value, _) in U32_TEST_CASES {
+		out.write_u32_vu128(*value).unwrap();
+	}
+	for (value, _) in I64_TEST_CASES {
+		out.write_i64_vu128(*value).unwrap();
+	}
+	out.write_f64_vu128(2.5).unwrap();
+	out.write_u128_vu128(u128::MAX).unwrap();
+	out.write_vu128(-1i8).unwrap();
+
+	let mut expect = Vec::new();
+	for (_, encoded_value) in U32_TEST_CASES {
+		expect.extend_from_slice(encoded_value);
+	}
+	for (_, encoded_value) in I64_TEST_CASES {
+		expect.extend_from_slice(encoded_value);
+	}
+	assert_eq!(&out[..expect.len()], &expect[..]);
+
+	let mut input = OneByteReader(&out);
+	for (value, _) in U32_TEST_CASES {
+		assert_eq!(input.read_u32_vu128().unwrap(), *value);
+	}
+	for (value, _) in I64_TEST_CASES {
+		assert_eq!(input.read_i64_vu128().unwrap(), *value);
+	}
+	assert_eq!(input.read_f64_vu128().unwrap(), 2.5);
+	assert_eq!(input.read_u128_vu128().unwrap(), u128::MAX);
+	assert_eq!(input.read_vu128::<i8>().unwrap(), -1);
+	assert!(input.0.is_empty());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_io_ext_errors() {
+	use std::io::ErrorKind;
+	use vu128::ReadVu128Ext;
+
+	let mut input: &[u8] = &[];
+	let err = input.read_u32_vu128().unwrap_err();
+	assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+	let mut input: &[u8] = &[0xF3, 0x00, 0x00];
+	let err = input.read_u32_vu128().unwrap_err();
+	assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+	let mut input: &[u8] = &[0xF4, 0x00, 0x00, 0x00, 0x00, 0x01, 0x7F];
+	let err = input.read_u32_vu128().unwrap_err();
+	assert_eq!(err.kind(), ErrorKind::InvalidData);
+	let inner = err.get_ref().unwrap();
+	let decode_err = inner.downcast_ref::<vu128::DecodeError>();
+	let expect = vu128::DecodeErrorKind::Overflow {
+		encoded_len: 6,
+		max_len: 5,
+	};
+	assert_eq!(decode_err.unwrap().kind(), expect);
+	assert_eq!(input.read_u32_vu128().unwrap(), 0x7F);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_buf_read_ext() {
+	use std::io::BufReader;
+	use vu128::{
+		BufReadVu128Ext,
+		WriteVu128Ext,
+	};
+
+	let mut out = Vec::new();
+	for (value, _) in U64_TEST_CASES {
+		out.write_u64_vu128(*value).unwrap();
+	}
+	for (value, _) in I64_TEST_CASES {
+		out.write_i64_vu128(*value).unwrap();
+	}
+	out.write_u128_vu128(u128::MAX).unwrap();
+
+	// Small capacities force values to straddle the end of the buffer.
+	for capacity in [1, 2, 3, 7, 16, 1024] {
+		let mut input = BufReader::with_capacity(capacity, &out[..]);
+		for (value, _) in U64_TEST_CASES {
+			assert_eq!(input.read_u64_vu128().unwrap(), *value);
+		}
+		for (value, _) in I64_TEST_CASES {
+			assert_eq!(input.read_i64_vu128().unwrap(), *value);
+		}
+		assert_eq!(input.read_u128_vu128().unwrap(), u128::MAX);
+
+		let err = input.read_u64_vu128().unwrap_err();
+		assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+	}
+
+	let mut input = BufReader::new(OneByteReader(&out));
+	for (value, _) in U64_TEST_CASES {
+		assert_eq!(input.read_vu128::<u64>().unwrap(), *value);
+	}
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_buf_read_ext_with_read_ext() {
+	use std::io::BufReader;
+	use vu128::{
+		BufReadVu128Ext,
+		ReadVu128Ext,
+	};
+
+	// With both traits in scope, the trait must be named explicitly.
+	let buf: &[u8] = &[0xB9, 0xC0, 0x01, 0x7F];
+	let mut input = BufReader::with_capacity(1, buf);
+	assert_eq!(BufReadVu128Ext::read_u32_vu128(&mut input).unwrap(), 12345);
+	assert_eq!(ReadVu128Ext::read_i64_vu128(&mut input).unwrap(), -1);
+	assert_eq!(BufReadVu128Ext::read_vu128::<u8>(&mut input).unwrap(), 0x7F);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_buf_read_ext_errors() {
+	use std::io::{
+		BufReader,
+		ErrorKind,
+	};
+	use vu128::BufReadVu128Ext;
+
+	for capacity in [1, 1024] {
+		let buf: &[u8] = &[0xF3, 0x00, 0x00];
+		let mut input = BufReader::with_capacity(capacity, buf);
+		let err = input.read_u32_vu128().unwrap_err();
+		assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+		let buf: &[u8] = &[0xF4, 0x00, 0x00, 0x00, 0x00, 0x01, 0x7F];
+		let mut input = BufReader::with_capacity(capacity, buf);
+		let err = input.read_u32_vu128().unwrap_err();
+		assert_eq!(err.kind(), ErrorKind::InvalidData);
+		assert_eq!(input.read_u32_vu128().unwrap(), 0x7F);
+	}
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_vec_ext() {
+	use vu128::VecVu128Ext;
+
+	let mut out = vec![0xAA];
+	let mut expect = vec![0xAA];
+	for (value, encoded_value) in U32_TEST_CASES {
+		assert_eq!(out.push_u32_vu128(*value), encoded_value.len());
+		expect.extend_from_slice(encoded_value);
+	}
+	for (value, encoded_value) in I64_TEST_CASES {
+		assert_eq!(out.push_i64_vu128(*value), encoded_value.len());
+		expect.extend_from_slice(encoded_value);
+	}
+	for (value, encoded_value) in F64_TEST_CASES {
+		assert_eq!(out.push_vu128(*value), encoded_value.len());
+		expect.extend_from_slice(encoded_value);
+	}
+	assert_eq!(out, expect);
+
+	let mut out = Vec::new();
+	assert_eq!(out.push_u128_vu128(u128::MAX), 17);
+	assert_eq!(vu128::decode_u128_from_slice(&out), Ok((u128::MAX, 17)));
+}
+
+fn check_vu128_trait<T>(value: T, encoded_value: &[u8])
+where
+	T: vu128::Vu128 + PartialEq + fmt::Debug,
+{
+	let mut buf = T::Buf::default();
+	let len = T::encode(&mut buf, value);
+	assert_eq!(&buf.as_ref()[..len], encoded_value);
+	assert_eq!(T::encoded_len(value), len);
+	assert_eq!(T::decode(&buf), (value, len));
+	assert_eq!(T::decode_from_slice(encoded_value), Ok((value, len)));
+}
+
+#[test]
+fn test_vu128_trait() {
+	use vu128::Vu128;
+
+	for (value, encoded_value) in U8_TEST_CASES {
+		check_vu128_trait(*value, encoded_value);
+	}
+	for (value, encoded_value) in U16_TEST_CASES {
+		check_vu128_trait(*value, encoded_value);
+	}
+	for (value, encoded_value) in I8_TEST_CASES {
+		check_vu128_trait(*value, encoded_value);
+	}
+	for (value, encoded_value) in I16_TEST_CASES {
+		check_vu128_trait(*value, encoded_value);
+	}
+	for (value, encoded_value) in U32_TEST_CASES {
+		check_vu128_trait(*value, encoded_value);
+		check_vu128_trait(*value as u128, encoded_value);
+	}
+	for (value, encoded_value) in U64_TEST_CASES {
+		check_vu128_trait(*value, encoded_value);
+	}
+	for (value, encoded_value) in U32_TEST_CASES {
+		check_vu128_trait(*value as usize, encoded_value);
+	}
+	for (value, encoded_value) in I32_TEST_CASES {
+		check_vu128_trait(*value, encoded_value);
+		check_vu128_trait(*value as isize, encoded_value);
+	}
+	for (value, encoded_value) in I64_TEST_CASES {
+		check_vu128_trait(*value, encoded_value);
+		check_vu128_trait(*value as i128, encoded_value);
+	}
+	for (value, encoded_value) in F32_TEST_CASES {
+		check_vu128_trait(*value, encoded_value);
+	}
+	for (value, encoded_value) in F64_TEST_CASES {
+		check_vu128_trait(*value, encoded_value);
+	}
+
+	assert_eq!(u8::MAX_LEN, vu128::MAX_LEN_U8);
+	assert_eq!(i16::MAX_LEN, vu128::MAX_LEN_U16);
+	assert_eq!(u32::MAX_LEN, vu128::MAX_LEN_U32);
+	assert_eq!(i64::MAX_LEN, vu128::MAX_LEN_U64);
+	assert_eq!(f64::MAX_LEN, vu128::MAX_LEN_U64);
+	assert_eq!(i128::MAX_LEN, vu128::MAX_LEN_U128);
+}
+
+#[test]
+fn test_decode_error_display() {
+	let err = vu128::decode_u32_from_slice(&[0xF3, 0x00]).unwrap_err();
+	assert_eq!(
+		err.to_string(),
+		"truncated input (needed 5 bytes, 2 available) at offset 0",
+	);
+
+	let err = vu128::decode_u32_from_slice(&[0xF4, 0, 0, 0, 0, 1]).unwrap_err();
+	assert_eq!(
+		err.to_string(),
+		"value overflows target type (encoded length 6, maximum 5) at offset 0",
+	);
+
+	let err = vu128::decode_u8_from_slice(&[0x80, 0x04]).unwrap_err();
+	assert_eq!(
+		err.to_string(),
+		"value out of range for target type at offset 0",
+	);
+
+	let err = vu128::decode_u32_canonical(&[0xF0, 0x80]).unwrap_err();
+	assert_eq!(
+		err.to_string(),
+		"non-canonical encoding (binary length prefix for value less than 2^28) at offset 0",
+	);
+
+	let input = [0x01, 0xF4, 0x00, 0x00, 0x00, 0x00, 0x01];
+	let err = vu128::decode_u32_slice(&input, &mut [0; 2]).unwrap_err();
+	assert_eq!(
+		err.to_string(),
+		"value overflows target type (encoded length 6, maximum 5) at offset 1 (after 1 values)",
+	);
+}
+
+trait ArgFmt: fmt::Debug {
+	fn arg_fmt(&self) -> String {
+		format!("{:?}", self)
+	}
+}
+
+impl ArgFmt for u8 {
+	fn arg_fmt(&self) -> String {
+		format!("0x{:02X?}", self)
+	}
+}
+
+impl ArgFmt for i8 {}
+
+impl ArgFmt for u16 {
+	fn arg_fmt(&self) -> String {
+		format!("0x{:04X?}", self)
+	}
+}
+
+impl ArgFmt for i16 {}
+
+impl ArgFmt for u32 {
+	fn arg_fmt(&self) -> String {
+		format!("0x{:08X?}", self)
+	}
+}
+
+impl ArgFmt for i32 {}
+
+impl ArgFmt for u64 {
+	fn arg_fmt(&self) -> String {
+		format!("0x{:016X?}", self)
+	}
+}
+impl ArgFmt for i64 {}
+
+impl ArgFmt for u128 {
+	fn arg_fmt(&self) -> String {
+		format!("0x{:032X?}", self)
+	}
+}
+impl ArgFmt for i128 {}
+
+impl ArgFmt for &[u8] {
+	fn arg_fmt(&self) -> String {
+		format!("{:?}", HexArray(self))
+	}
+}
+
+impl ArgFmt for f32 {}
+
+impl ArgFmt for f64 {}
+
+impl ArgFmt for usize {}
+
+impl ArgFmt for isize {}
+
+impl<T1: ArgFmt, T2: ArgFmt> ArgFmt for (T1, T2) {
+	fn arg_fmt(&self) -> String {
+		format!("({}, {})", self.0.arg_fmt(), self.1.arg_fmt())
+	}
+}
+
+impl<T: ArgFmt> ArgFmt for Result<T, vu128::DecodeError> {
+	fn arg_fmt(&self) -> String {
+		match self {
+			Ok(value) => format!("Ok({})", value.arg_fmt()),
+			Err(err) => format!("Err({:?})", err),
+		}
 	}
 }
 